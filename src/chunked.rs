@@ -0,0 +1,857 @@
+use core::{marker::PhantomData, mem::MaybeUninit, ops::RangeBounds, ptr};
+
+use crate::{fold_chain::Iter, fold_list::{range_to_start_end, try_range_to_start_end, FoldList, FoldListSlice}, fold_settings::{FoldSettings, FoldSettingsStruct}, misc::{EmptyFn, False, IndexOutOfBounds, TupleFun}};
+
+/// Up to `N` consecutive elements of a [`ChunkedFoldList`], stored inline, together with their combined delta.
+///
+/// A chunk can be read like a slice of its elements, via [`Deref`](core::ops::Deref), but can only be changed by the `ChunkedFoldList` which holds it, so that its delta stays up to date.
+pub struct Chunk<T,D,const N: usize> {
+    values: [MaybeUninit<T>; N],
+    len: usize,
+    delta: D,
+}
+
+impl<T,D,const N: usize> Chunk<T,D,N> {
+    fn new(empty: D) -> Self {
+        const { assert!(N > 0, "Chunks must be able to hold at least one element") };
+        Self { values: [const { MaybeUninit::uninit() }; N], len: 0, delta: empty }
+    }
+
+    fn from_value(value: T, settings: &impl FoldSettings<T,D>) -> Self {
+        let mut ret = Self::new(settings.delta_of(&value));
+        ret.values[0].write(value);
+        ret.len = 1;
+        ret
+    }
+
+    /// Get the elements of this chunk, from left to right.
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { core::slice::from_raw_parts(self.values.as_ptr() as *const T, self.len) }
+    }
+
+    /// Get the fold of the elements of this chunk.
+    pub fn delta(&self) -> &D {
+        &self.delta
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut T {
+        self.values.as_mut_ptr() as *mut T
+    }
+
+    fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    fn recalc_delta(&mut self, settings: &impl FoldSettings<T,D>) {
+        let mut iter = self.as_slice().iter();
+        let first = iter.next().map_or_else(|| settings.empty(), |t| settings.delta_of(t));
+        self.delta = iter.fold(first, |acc,t| settings.op(acc, settings.delta_of(t)));
+    }
+
+    //the caller must recalculate the delta afterwards
+    fn insert_without_recalc(&mut self, index: usize, value: T) {
+        debug_assert!(index <= self.len && !self.is_full());
+        unsafe {
+            let p = self.as_mut_ptr().add(index);
+            ptr::copy(p, p.add(1), self.len - index);
+            p.write(value);
+        }
+        self.len += 1;
+    }
+
+    //inserts `value` at `index`, and if this chunk was full, splits off and returns the right half of it first
+    fn insert_or_split(&mut self, index: usize, value: T, settings: &impl FoldSettings<T,D>) -> Option<Self> {
+        if !self.is_full() {
+            self.insert_without_recalc(index, value);
+            self.recalc_delta(settings);
+            return None;
+        }
+        //index < N here, so if N is 1, index is 0 and the value goes left
+        let mid = N / 2;
+        let mut right = self.split_off(mid, settings);
+        if index <= mid {
+            self.insert_without_recalc(index, value);
+            self.recalc_delta(settings);
+        } else {
+            right.insert_without_recalc(index - mid, value);
+            right.recalc_delta(settings);
+        }
+        Some(right)
+    }
+
+    fn remove(&mut self, index: usize, settings: &impl FoldSettings<T,D>) -> T {
+        debug_assert!(index < self.len);
+        let ret = unsafe {
+            let p = self.as_mut_ptr().add(index);
+            let ret = p.read();
+            ptr::copy(p.add(1), p, self.len - index - 1);
+            ret
+        };
+        self.len -= 1;
+        self.recalc_delta(settings);
+        ret
+    }
+
+    //the delta is recalculated afterwards even if f panics
+    fn update<R, Settings: FoldSettings<T,D>>(&mut self, index: usize, settings: &Settings, f: impl FnOnce(&mut T) -> R) -> R {
+        let chunk: *mut Self = self;
+        let _guard = RecalcDeltaGuard { chunk, settings };
+        f(unsafe { &mut *(*chunk).as_mut_ptr().add(index) })
+    }
+
+    //moves the elements at `index` and above into a new chunk
+    fn split_off(&mut self, index: usize, settings: &impl FoldSettings<T,D>) -> Self {
+        let mut ret = Self::new(settings.empty());
+        unsafe {
+            ptr::copy_nonoverlapping(self.as_mut_ptr().add(index), ret.as_mut_ptr(), self.len - index);
+        }
+        ret.len = self.len - index;
+        self.len = index;
+        self.recalc_delta(settings);
+        ret.recalc_delta(settings);
+        ret
+    }
+
+    //moves every element of `other` to the right of this chunk's elements, which must have room for them
+    fn append(&mut self, mut other: Self, settings: &impl FoldSettings<T,D>) {
+        debug_assert!(self.len + other.len <= N);
+        unsafe {
+            ptr::copy_nonoverlapping(other.as_mut_ptr(), self.as_mut_ptr().add(self.len), other.len);
+        }
+        self.len += other.len;
+        other.len = 0;
+        self.delta = settings.op(core::mem::replace(&mut self.delta, settings.empty()), core::mem::replace(&mut other.delta, settings.empty()));
+    }
+}
+
+//recalculates a chunk's delta when dropped, so that it stays right even if a closure mutating one of its elements panics
+struct RecalcDeltaGuard<'a,T,D,Settings: FoldSettings<T,D>,const N: usize> {
+    chunk: *mut Chunk<T,D,N>,
+    settings: &'a Settings,
+}
+
+impl<'a,T,D,Settings: FoldSettings<T,D>,const N: usize> Drop for RecalcDeltaGuard<'a,T,D,Settings,N> {
+    fn drop(&mut self) {
+        unsafe { (*self.chunk).recalc_delta(self.settings) }
+    }
+}
+
+impl<T,D,const N: usize> core::ops::Deref for Chunk<T,D,N> {
+    type Target = [T];
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T,D,const N: usize> Drop for Chunk<T,D,N> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.as_mut_ptr(), self.len));
+        }
+    }
+}
+
+impl<T: Clone, D: Clone, const N: usize> Clone for Chunk<T,D,N> {
+    fn clone(&self) -> Self {
+        let mut ret = Self::new(self.delta.clone());
+        for (i,t) in self.as_slice().iter().enumerate() {
+            ret.values[i].write(t.clone());
+            //incremented as we go, so that a panicking clone only drops what was written
+            ret.len = i + 1;
+        }
+        ret
+    }
+}
+
+impl<T: core::fmt::Debug, D, const N: usize> core::fmt::Debug for Chunk<T,D,N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl<'a, T, D, const N: usize> IntoIterator for &'a Chunk<T,D,N> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+/// A wrapper around a [`FoldSettings`] value which folds [`Chunk`]s of elements instead of single elements, by replacing its element type, `T`, with `Chunk<T,D,N>`, and its delta type, `D`, with `(usize,D)`.
+///
+/// The `usize` part of the delta is the number of elements, and the delta of a chunk is the combined delta that it stores, so `delta_of` is *O*(1).
+#[derive(Clone,Copy,Default)]
+pub struct ChunkSettings<S>(pub S);
+impl<T, D: Clone, S: FoldSettings<T,D>, const N: usize> FoldSettings<Chunk<T,D,N>,(usize,D)> for ChunkSettings<S> {
+    fn op(&self, (n,a): (usize,D), (m,b): (usize,D)) -> (usize,D) {
+        (n + m, self.0.op(a,b))
+    }
+    fn delta_of(&self, t: &Chunk<T,D,N>) -> (usize,D) {
+        (t.len, t.delta.clone())
+    }
+    fn empty(&self) -> (usize,D) {
+        (0,self.0.empty())
+    }
+}
+
+/// A list like [`FoldList`], whose elements are stored in [`Chunk`]s of up to `N` elements each, rather than each in its own tree node.
+///
+/// This takes much less memory per element when `T` is small, and iterating over it chases far fewer pointers.
+/// In exchange, operations on single elements take *O*(N + log(n)) time instead of *O*(log(n)), since the chunk holding the element has to be refolded.
+///
+/// Indices, views and searches are still in terms of elements, not chunks: [`view_range`](ChunkedFoldList::view_range), [`find_left`](ChunkedFoldList::find_left) and [`split_off_where`](ChunkedFoldList::split_off_where)
+/// land on the same elements that they would for a `FoldList` with the same contents; see [`ChunkedFoldListSlice`].
+///
+/// Adjacent chunks are merged when removals leave them less than half full, so every chunk but a few stays at least half full.
+#[derive(Clone)]
+pub struct ChunkedFoldList<T, D: Clone, Settings: FoldSettings<T,D>, const N: usize = 16> {
+    //indices of this list are indices of chunks, whereas the `usize` part of its deltas counts elements
+    underlying: FoldList<Chunk<T,D,N>, (usize,D), ChunkSettings<Settings>>,
+}
+
+impl<T,D: Clone, OP: Fn(D,D)->D + Copy, DeltaOf: Fn(&T)->D + Copy, Empty: Fn()->D + Copy, const N: usize> ChunkedFoldList<T,D,FoldSettingsStruct<T,D,TupleFun<OP>,DeltaOf,EmptyFn<Empty>>,N> {
+    /// Create a new empty `ChunkedFoldList`, given the closures for [Settings](crate#fold-settings).
+    pub fn new(op: OP, delta_of: DeltaOf, empty_delta_fn: Empty) -> Self {
+        ChunkedFoldList::from_settings(FoldSettingsStruct {
+            op_closure: TupleFun(op),
+            t2d_closure: delta_of,
+            empty_closure: EmptyFn(empty_delta_fn),
+            _m: PhantomData,
+        })
+    }
+
+    /// Create a new `ChunkedFoldList`, given the closures for [Settings](crate#fold-settings), and fill it using an iterator, from left to right.
+    pub fn from_iter(op: OP, delta_of: DeltaOf, empty_delta_fn: Empty, iter: impl Iterator<Item=T>) -> Self {
+        let mut ret = Self::new(op,delta_of,empty_delta_fn);
+        ret.append_right_from_iter(iter);
+        ret
+    }
+}
+
+impl<T, D: Clone, Settings: FoldSettings<T,D>, const N: usize> ChunkedFoldList<T, D, Settings, N> {
+    /// Create a new empty `ChunkedFoldList`, with the specified [Settings](crate#fold-settings).
+    pub fn from_settings(settings: Settings) -> Self {
+        Self { underlying: FoldList::from_settings(ChunkSettings(settings)) }
+    }
+
+    /// Get the [Settings](crate#fold-settings) of this list.
+    pub fn get_settings(&self) -> Settings {
+        self.underlying.get_settings().0
+    }
+
+    //finds the chunk holding the element at `index`, along with the number of chunks and the number of elements before it
+    fn locate(&self, index: usize) -> Option<(usize, usize, &Chunk<T,D,N>)> {
+        let (chunk, (chunks_before, elements_before)) = self.underlying.as_sized_chain()
+            .view_simplify_with_shortcut(
+                |(c,(n,_)): &(usize,(usize,D))| (*c,*n),
+                |((c,n),(c2,n2)): ((usize,usize),(usize,usize))| (c + c2, n + n2),
+                |()| (0,0),
+                |chunk: &Chunk<T,D,N>| (1,chunk.len)
+            )
+            .find_left_consume(|&(_,n)| n > index)?;
+        Some((chunks_before, index - elements_before, chunk))
+    }
+
+    //like locate, but with an error if `index` is out of bounds
+    fn try_locate(&self, index: usize) -> Result<(usize, usize, &Chunk<T,D,N>), IndexOutOfBounds> {
+        self.locate(index).ok_or_else(|| IndexOutOfBounds { index, len: self.len() })
+    }
+
+    /// Get the number of elements in this list. This is *O*(1).
+    pub fn len(&self) -> usize {
+        self.underlying.view_simplify_with_shortcut(
+            |(n,_): &(usize,D)| *n,
+            |(a,b): (usize,usize)| a + b,
+            |()| 0,
+            |chunk: &Chunk<T,D,N>| chunk.len
+        ).fold()
+    }
+
+    /// Get the number of chunks that this list's elements are stored in.
+    pub fn chunk_count(&self) -> usize {
+        self.underlying.len()
+    }
+
+    /// Returns `true` if this list has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.underlying.is_empty()
+    }
+
+    /// Get the fold of every element in this list.
+    pub fn fold(&self) -> D {
+        self.underlying.fold().1
+    }
+
+    /// Get the fold of the elements in `range`. This is *O*(N + log(n)).
+    ///
+    /// Panics if `range` is out of bounds.
+    pub fn fold_range(&self, range: impl RangeBounds<usize>) -> D {
+        self.view_range(range).fold()
+    }
+
+    /// Get the leftmost element, or [`None`] if this list is empty.
+    pub fn left(&self) -> Option<&T> {
+        self.underlying.left()?.first()
+    }
+
+    /// Get the rightmost element, or [`None`] if this list is empty.
+    pub fn right(&self) -> Option<&T> {
+        self.underlying.right()?.last()
+    }
+
+    /// Get the element at index `index`. This is *O*(log(n)).
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// For the non-panicking version, see [`try_get`](ChunkedFoldList::try_get).
+    pub fn get(&self, index: usize) -> &T {
+        self.try_get(index).unwrap_or_else(|e| panic!("{}",e))
+    }
+
+    /// Get the element at index `index`, or an [`IndexOutOfBounds`] error if there is no such element. This is *O*(log(n)).
+    pub fn try_get(&self, index: usize) -> Result<&T, IndexOutOfBounds> {
+        let (_, offset, chunk) = self.try_locate(index)?;
+        Ok(&chunk[offset])
+    }
+
+    /// Find the first element from the left whose inclusion causes the fold to satisfy `predicate`, like [`find_left`](FoldListSlice::find_left).
+    ///
+    /// Returns its index, a reference to it, and the fold of every element before it, or [`None`] if no element causes the fold to satisfy `predicate`.
+    /// `predicate` should be monotonic, as in [`view_take_left_until`](FoldListSlice::view_take_left_until). This is *O*(N + log(n)).
+    pub fn find_left(&self, predicate: impl Fn(&D)->bool) -> Option<(usize, &T, D)> {
+        self.as_imm().find_left(predicate)
+    }
+
+    /// Find the first element from the right whose inclusion causes the fold to satisfy `predicate`, like [`find_right`](FoldListSlice::find_right).
+    ///
+    /// Returns its index (from the left), a reference to it, and the fold of every element after it, or [`None`] if no element causes the fold to satisfy `predicate`. This is *O*(N + log(n)).
+    pub fn find_right(&self, predicate: impl Fn(&D)->bool) -> Option<(usize, &T, D)> {
+        self.as_imm().find_right(predicate)
+    }
+
+    /// Get an immutable view of every element of this list, which can then be narrowed down; see [`ChunkedFoldListSlice`].
+    pub fn as_imm(&self) -> ChunkedFoldListSlice<'_, T, D, Settings, N> {
+        ChunkedFoldListSlice { list: self, start: 0, end: self.len() }
+    }
+    /// An alias of [`view_take_left`](ChunkedFoldListSlice::view_take_left).
+    pub fn view_take_left(&self, n: usize) -> ChunkedFoldListSlice<'_, T, D, Settings, N> {
+        self.as_imm().view_take_left(n)
+    }
+    /// An alias of [`view_take_right`](ChunkedFoldListSlice::view_take_right).
+    pub fn view_take_right(&self, n: usize) -> ChunkedFoldListSlice<'_, T, D, Settings, N> {
+        self.as_imm().view_take_right(n)
+    }
+    /// An alias of [`view_drop_left`](ChunkedFoldListSlice::view_drop_left).
+    pub fn view_drop_left(&self, n: usize) -> ChunkedFoldListSlice<'_, T, D, Settings, N> {
+        self.as_imm().view_drop_left(n)
+    }
+    /// An alias of [`view_drop_right`](ChunkedFoldListSlice::view_drop_right).
+    pub fn view_drop_right(&self, n: usize) -> ChunkedFoldListSlice<'_, T, D, Settings, N> {
+        self.as_imm().view_drop_right(n)
+    }
+    /// An alias of [`view_range`](ChunkedFoldListSlice::view_range).
+    pub fn view_range(&self, range: impl RangeBounds<usize>) -> ChunkedFoldListSlice<'_, T, D, Settings, N> {
+        self.as_imm().view_range(range)
+    }
+    /// An alias of [`try_view_range`](ChunkedFoldListSlice::try_view_range).
+    pub fn try_view_range(&self, range: impl RangeBounds<usize>) -> Option<ChunkedFoldListSlice<'_, T, D, Settings, N>> {
+        self.as_imm().try_view_range(range)
+    }
+    /// An alias of [`view_take_left_until`](ChunkedFoldListSlice::view_take_left_until).
+    pub fn view_take_left_until(&self, predicate: impl Fn(&D)->bool) -> ChunkedFoldListSlice<'_, T, D, Settings, N> {
+        self.as_imm().view_take_left_until(predicate)
+    }
+    /// An alias of [`view_drop_left_until`](ChunkedFoldListSlice::view_drop_left_until).
+    pub fn view_drop_left_until(&self, predicate: impl Fn(&D)->bool) -> ChunkedFoldListSlice<'_, T, D, Settings, N> {
+        self.as_imm().view_drop_left_until(predicate)
+    }
+    /// An alias of [`view_take_right_until`](ChunkedFoldListSlice::view_take_right_until).
+    pub fn view_take_right_until(&self, predicate: impl Fn(&D)->bool) -> ChunkedFoldListSlice<'_, T, D, Settings, N> {
+        self.as_imm().view_take_right_until(predicate)
+    }
+    /// An alias of [`view_drop_right_until`](ChunkedFoldListSlice::view_drop_right_until).
+    pub fn view_drop_right_until(&self, predicate: impl Fn(&D)->bool) -> ChunkedFoldListSlice<'_, T, D, Settings, N> {
+        self.as_imm().view_drop_right_until(predicate)
+    }
+
+    /// Iterate over the elements of this list, from left to right.
+    pub fn iter(&self) -> core::iter::Flatten<Iter<'_, False, Chunk<T,D,N>, (usize,(usize,D))>> {
+        self.underlying.iter().flatten()
+    }
+
+    /// The `FoldList` of chunks which underlies this list. Its indices are indices of chunks, whereas the `usize` part of its deltas counts elements.
+    ///
+    /// It's read-only, since changing the chunks directly could leave them empty or unbalanced.
+    pub fn chunk_list(&self) -> &FoldList<Chunk<T,D,N>, (usize,D), ChunkSettings<Settings>> {
+        &self.underlying
+    }
+
+    /// Iterate over the chunks of this list, from left to right.
+    pub fn chunks(&self) -> Iter<'_, False, Chunk<T,D,N>, (usize,(usize,D))> {
+        self.underlying.iter()
+    }
+
+    /// Add an element to the left end of this list.
+    pub fn append_left(&mut self, value: T) {
+        let settings = self.get_settings();
+        let value = self.underlying.update_left(|chunk| match chunk {
+            Some(chunk) if !chunk.is_full() => {
+                chunk.insert_without_recalc(0, value);
+                chunk.recalc_delta(&settings);
+                None
+            },
+            _ => Some(value),
+        });
+        if let Some(value) = value {
+            self.underlying.append_left(Chunk::from_value(value, &settings));
+        }
+    }
+
+    /// Add an element to the right end of this list.
+    pub fn append_right(&mut self, value: T) {
+        let settings = self.get_settings();
+        let value = self.underlying.update_right(|chunk| match chunk {
+            Some(chunk) if !chunk.is_full() => {
+                let (old, new) = (core::mem::replace(&mut chunk.delta, settings.empty()), settings.delta_of(&value));
+                chunk.delta = settings.op(old, new);
+                chunk.insert_without_recalc(chunk.len, value);
+                None
+            },
+            _ => Some(value),
+        });
+        if let Some(value) = value {
+            self.underlying.append_right(Chunk::from_value(value, &settings));
+        }
+    }
+
+    /// Add every element of `iter` to the right end of this list, from left to right.
+    /// The rightmost chunk is filled up first, and then each new chunk is filled before the next is started.
+    pub fn append_right_from_iter(&mut self, iter: impl Iterator<Item=T>) {
+        let settings = self.get_settings();
+        let mut iter = iter.peekable();
+        //collected before touching the rightmost chunk, so that a panicking iterator can't leave its delta stale
+        let room = self.underlying.right().map_or(0, |chunk| N - chunk.len);
+        let mut top_up = Chunk::new(settings.empty());
+        while let Some(value) = (top_up.len < room).then(|| iter.next()).flatten() {
+            top_up.insert_without_recalc(top_up.len, value);
+        }
+        if top_up.len > 0 {
+            top_up.recalc_delta(&settings);
+            self.underlying.update_right(|chunk| chunk.unwrap().append(top_up, &settings));
+        }
+        let chunks = core::iter::from_fn(|| {
+            iter.peek()?;
+            let mut chunk = Chunk::new(settings.empty());
+            while let Some(value) = (!chunk.is_full()).then(|| iter.next()).flatten() {
+                chunk.insert_without_recalc(chunk.len, value);
+            }
+            chunk.recalc_delta(&settings);
+            Some(chunk)
+        });
+        self.underlying.append_right_from_iter(chunks);
+    }
+
+    /// Remove the leftmost element, or return [`None`] if this list is empty.
+    pub fn pop_left(&mut self) -> Option<T> {
+        if self.is_empty() {None} else {Some(self.remove_at(0))}
+    }
+
+    /// Remove the rightmost element, or return [`None`] if this list is empty.
+    pub fn pop_right(&mut self) -> Option<T> {
+        let len = self.len();
+        if len == 0 {None} else {Some(self.remove_at(len - 1))}
+    }
+
+    /// Insert an element at index `index`, so that the elements formerly at `index` and above are shifted one to the right. This is *O*(N + log(n)).
+    ///
+    /// Panics if `index` is outside ```0..=self.len()```.
+    pub fn insert_at(&mut self, index: usize, value: T) {
+        self.try_insert_at(index, value).unwrap_or_else(|e| panic!("{}",e))
+    }
+
+    /// Like [`insert_at`](ChunkedFoldList::insert_at), but returns an [`IndexOutOfBounds`] error instead of panicking.
+    pub fn try_insert_at(&mut self, index: usize, value: T) -> Result<(), IndexOutOfBounds> {
+        let len = self.len();
+        if index == len {
+            self.append_right(value);
+            return Ok(());
+        }
+        let (chunk_index, offset, _) = self.try_locate(index)?;
+        let settings = self.get_settings();
+        if let Some(right) = self.underlying.update_at(chunk_index, |chunk| chunk.insert_or_split(offset, value, &settings)) {
+            self.underlying.insert_at(chunk_index + 1, right);
+        }
+        Ok(())
+    }
+
+    /// Remove and return the element at index `index`. This is *O*(N + log(n)).
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove_at(&mut self, index: usize) -> T {
+        self.try_remove_at(index).unwrap_or_else(|e| panic!("{}",e))
+    }
+
+    /// Like [`remove_at`](ChunkedFoldList::remove_at), but returns an [`IndexOutOfBounds`] error instead of panicking.
+    pub fn try_remove_at(&mut self, index: usize) -> Result<T, IndexOutOfBounds> {
+        let (chunk_index, offset, _) = self.try_locate(index)?;
+        let settings = self.get_settings();
+        let (ret, remaining) = self.underlying.update_at(chunk_index, |chunk| (chunk.remove(offset, &settings), chunk.len));
+        if remaining == 0 {
+            self.underlying.remove_at(chunk_index);
+        } else if remaining < N / 2 && chunk_index + 1 < self.underlying.len() && remaining + self.underlying[chunk_index + 1].len <= N {
+            let right = self.underlying.remove_at(chunk_index + 1);
+            self.underlying.update_at(chunk_index, |chunk| chunk.append(right, &settings));
+        }
+        Ok(ret)
+    }
+
+    /// Mutate the element at index `index` via a closure, and return the result of the closure. This is *O*(N + log(n)).
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn update_at<R>(&mut self, index: usize, f: impl FnOnce(&mut T)->R) -> R {
+        self.try_update_at(index, f).unwrap_or_else(|e| panic!("{}",e))
+    }
+
+    /// Like [`update_at`](ChunkedFoldList::update_at), but returns an [`IndexOutOfBounds`] error instead of panicking.
+    pub fn try_update_at<R>(&mut self, index: usize, f: impl FnOnce(&mut T)->R) -> Result<R, IndexOutOfBounds> {
+        let (chunk_index, offset, _) = self.try_locate(index)?;
+        let settings = self.get_settings();
+        Ok(self.underlying.update_at(chunk_index, |chunk| chunk.update(offset, &settings, f)))
+    }
+
+    /// Replace the element at index `index` with `value`, and return the previous element. This is *O*(N + log(n)).
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set_at(&mut self, index: usize, value: T) -> T {
+        self.update_at(index, |t| core::mem::replace(t, value))
+    }
+
+    /// Like [`set_at`](ChunkedFoldList::set_at), but returns an [`IndexOutOfBounds`] error instead of panicking.
+    pub fn try_set_at(&mut self, index: usize, value: T) -> Result<T, IndexOutOfBounds> {
+        self.try_update_at(index, |t| core::mem::replace(t, value))
+    }
+
+    /// Split this list in two at index `index`, like [`FoldList::split_off`]. At most one chunk is split. This is *O*(N + log(n)).
+    ///
+    /// Panics if `index` is outside ```0..=self.len()```.
+    pub fn split_off(&mut self, index: usize) -> Self {
+        let len = self.len();
+        if index == len {
+            return Self::from_settings(self.get_settings());
+        }
+        let (chunk_index, offset, _) = self.try_locate(index).unwrap_or_else(|e| panic!("{}",e));
+        let settings = self.get_settings();
+        let mut right = self.underlying.split_off(chunk_index);
+        if offset != 0 {
+            let left = right.update_left(|chunk| {
+                let chunk = chunk.unwrap();
+                let right = chunk.split_off(offset, &settings);
+                core::mem::replace(chunk, right)
+            });
+            self.underlying.append_right(left);
+        }
+        Self { underlying: right }
+    }
+
+    /// Split this list in two at the first element which causes the fold from the left to satisfy `predicate`, like [`FoldList::split_off_where`].
+    ///
+    /// That element and everything to its right are removed and returned as a new list. This is *O*(N + log(n)).
+    pub fn split_off_where(&mut self, predicate: impl Fn(&D)->bool) -> Self {
+        let index = self.find_left(predicate).map_or_else(|| self.len(), |(i,_,_)| i);
+        self.split_off(index)
+    }
+
+    /// Move every element of `list` to the right end of this list. This is *O*(log(n)).
+    pub fn append_all_right(&mut self, list: Self) {
+        self.underlying.append_all_right(list.underlying);
+    }
+
+    /// Move every element of `list` to the left end of this list. This is *O*(log(n)).
+    pub fn append_all_left(&mut self, list: Self) {
+        self.underlying.append_all_left(list.underlying);
+    }
+}
+
+impl<T, D: Clone, Settings: FoldSettings<T,D>, const N: usize> core::ops::Index<usize> for ChunkedFoldList<T, D, Settings, N> {
+    type Output = T;
+    fn index(&self, index: usize) -> &T {
+        self.get(index)
+    }
+}
+
+impl<T: core::fmt::Debug, D: Clone, Settings: FoldSettings<T,D>, const N: usize> core::fmt::Debug for ChunkedFoldList<T, D, Settings, N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T, D: Clone, Settings: FoldSettings<T,D> + Default, const N: usize> Default for ChunkedFoldList<T, D, Settings, N> {
+    fn default() -> Self {
+        Self::from_settings(Settings::default())
+    }
+}
+
+impl<T, D: Clone, Settings: FoldSettings<T,D> + Default, const N: usize> FromIterator<T> for ChunkedFoldList<T, D, Settings, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut ret = Self::default();
+        ret.append_right_from_iter(iter.into_iter());
+        ret
+    }
+}
+
+impl<T, D: Clone, Settings: FoldSettings<T,D>, const N: usize> Extend<T> for ChunkedFoldList<T, D, Settings, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.append_right_from_iter(iter.into_iter());
+    }
+}
+
+impl<'a, T, D: Clone, Settings: FoldSettings<T,D>, const N: usize> IntoIterator for &'a ChunkedFoldList<T, D, Settings, N> {
+    type Item = &'a T;
+    type IntoIter = core::iter::Flatten<Iter<'a, False, Chunk<T,D,N>, (usize,(usize,D))>>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An immutable view into a range of a [`ChunkedFoldList`]'s elements, made with [`as_imm`](ChunkedFoldList::as_imm) or one of the list's `view_*` methods.
+///
+/// Like a [`FoldListSlice`], it can be narrowed down further, by a number of elements or by a predicate on the fold, and always lands on exact element boundaries, wherever the chunks happen to be split.
+/// Folds and searches over a view are *O*(N + log(n)), since at most two chunks are only partly inside it.
+///
+/// It isn't a [`FoldListSlice`] itself, and only has the methods below: it can't be reversed or simplified, and doesn't give access to the underlying [`FoldChain`](crate::fold_chain::FoldChain).
+/// For the full `FoldListSlice` API over whole chunks, use [`chunk_list`](ChunkedFoldList::chunk_list).
+pub struct ChunkedFoldListSlice<'a, T, D: Clone, Settings: FoldSettings<T,D>, const N: usize> {
+    list: &'a ChunkedFoldList<T, D, Settings, N>,
+    start: usize,
+    end: usize,
+}
+
+impl<'a, T, D: Clone, Settings: FoldSettings<T,D>, const N: usize> Clone for ChunkedFoldListSlice<'a, T, D, Settings, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<'a, T, D: Clone, Settings: FoldSettings<T,D>, const N: usize> Copy for ChunkedFoldListSlice<'a, T, D, Settings, N> {}
+
+impl<'a, T, D: Clone, Settings: FoldSettings<T,D>, const N: usize> ChunkedFoldListSlice<'a, T, D, Settings, N> {
+    /// Get the number of elements in this view. This is *O*(1).
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Returns `true` if this view has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    //the chunks holding this view's leftmost and rightmost elements, each with its index and the offset of that element in it
+    fn ends(&self) -> Option<((usize, usize, &'a Chunk<T,D,N>), (usize, usize, &'a Chunk<T,D,N>))> {
+        if self.is_empty() {
+            return None;
+        }
+        Some((self.list.locate(self.start).unwrap(), self.list.locate(self.end - 1).unwrap()))
+    }
+
+    /// Get the fold of every element in this view. This is *O*(N + log(n)).
+    pub fn fold(&self) -> D {
+        let settings = self.list.get_settings();
+        let Some(((first_chunk, first_offset, first), (last_chunk, last_offset, last))) = self.ends() else {
+            return settings.empty();
+        };
+        let fold_of = |elements: &[T]| elements.iter()
+            .map(|t| settings.delta_of(t))
+            .reduce(|a,b| settings.op(a,b))
+            .unwrap_or_else(|| settings.empty());
+        if first_chunk == last_chunk {
+            return fold_of(&first[first_offset..=last_offset]);
+        }
+        let left = if first_offset == 0 {first.delta.clone()} else {fold_of(&first[first_offset..])};
+        let middle = self.list.underlying.view_range(first_chunk + 1..last_chunk).fold().1;
+        let right = if last_offset + 1 == last.len {last.delta.clone()} else {fold_of(&last[..=last_offset])};
+        settings.op(settings.op(left, middle), right)
+    }
+
+    /// Get this view's leftmost element, or [`None`] if it is empty.
+    pub fn left(&self) -> Option<&'a T> {
+        (!self.is_empty()).then(|| self.list.get(self.start))
+    }
+
+    /// Get this view's rightmost element, or [`None`] if it is empty.
+    pub fn right(&self) -> Option<&'a T> {
+        (!self.is_empty()).then(|| self.list.get(self.end - 1))
+    }
+
+    /// Get the element at index `index` of this view. This is *O*(log(n)).
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn get(&self, index: usize) -> &'a T {
+        self.try_get(index).unwrap_or_else(|e| panic!("{}",e))
+    }
+
+    /// Get the element at index `index` of this view, or an [`IndexOutOfBounds`] error if there is no such element. This is *O*(log(n)).
+    pub fn try_get(&self, index: usize) -> Result<&'a T, IndexOutOfBounds> {
+        if index >= self.len() {
+            return Err(IndexOutOfBounds { index, len: self.len() });
+        }
+        self.list.try_get(self.start + index)
+    }
+
+    /// Iterate over the elements of this view, from left to right.
+    pub fn iter(&self) -> core::iter::Take<core::iter::Skip<core::iter::Flatten<Iter<'a, False, Chunk<T,D,N>, (usize,(usize,D))>>>> {
+        let (first_chunk, first_offset) = self.ends().map_or((0,0), |((c,o,_),_)| (c,o));
+        self.list.underlying.view_drop_left(first_chunk).into_iter().flatten().skip(first_offset).take(self.len())
+    }
+
+    //scans `elements` from the left, with `pre` as the fold of everything before them, for the first one whose inclusion satisfies `predicate`.
+    //`before` is the index of `elements[0]` in this view. If there is none, `pre` ends up as the fold including all of them
+    fn scan_left(settings: &Settings, elements: &'a [T], before: usize, pre: &mut D, predicate: &impl Fn(&D)->bool) -> Option<(usize, &'a T, D)> {
+        for (i,t) in elements.iter().enumerate() {
+            let next = settings.op(pre.clone(), settings.delta_of(t));
+            if predicate(&next) {
+                return Some((before + i, t, core::mem::replace(pre, next)));
+            }
+            *pre = next;
+        }
+        None
+    }
+
+    //the mirror image of scan_left, where `after` is the index in this view of the element right after `elements`
+    fn scan_right(settings: &Settings, elements: &'a [T], after: usize, post: &mut D, predicate: &impl Fn(&D)->bool) -> Option<(usize, &'a T, D)> {
+        for (i,t) in elements.iter().enumerate().rev() {
+            let next = settings.op(settings.delta_of(t), post.clone());
+            if predicate(&next) {
+                return Some((after - elements.len() + i, t, core::mem::replace(post, next)));
+            }
+            *post = next;
+        }
+        None
+    }
+
+    /// Find the first element from the left whose inclusion causes the fold from the left of this view to satisfy `predicate`, like [`find_left`](FoldListSlice::find_left).
+    ///
+    /// Returns its index in this view, a reference to it, and the fold of every element of this view before it, or [`None`] if the fold of this whole view doesn't satisfy `predicate`.
+    /// `predicate` should be monotonic, as in [`view_take_left_until`](FoldListSlice::view_take_left_until). This is *O*(N + log(n)).
+    pub fn find_left(&self, predicate: impl Fn(&D)->bool) -> Option<(usize, &'a T, D)> {
+        let settings = self.list.get_settings();
+        let ((first_chunk, first_offset, first), (last_chunk, last_offset, last)) = self.ends()?;
+        let mut pre = settings.empty();
+        if first_chunk == last_chunk {
+            return Self::scan_left(&settings, &first[first_offset..=last_offset], 0, &mut pre, &predicate);
+        }
+        if let Some(found) = Self::scan_left(&settings, &first[first_offset..], 0, &mut pre, &predicate) {
+            return Some(found);
+        }
+        let before = first.len - first_offset;
+        let middle = self.list.underlying.view_range(first_chunk + 1..last_chunk);
+        let Some((i, _, (n, d))) = middle.find_left(|(_,d)| predicate(&settings.op(pre.clone(), d.clone()))) else {
+            let (m, d) = middle.fold();
+            pre = settings.op(pre, d);
+            return Self::scan_left(&settings, &last[..=last_offset], before + m, &mut pre, &predicate);
+        };
+        let chunk = self.list.underlying.get(first_chunk + 1 + i);
+        let mut pre = settings.op(pre, d);
+        let (last, init) = chunk.split_last().unwrap();
+        //including the whole chunk satisfies the predicate, so if no other element does, it's the last one
+        Self::scan_left(&settings, init, before + n, &mut pre, &predicate)
+            .or_else(|| Some((before + n + init.len(), last, pre)))
+    }
+
+    /// Find the first element from the right whose inclusion causes the fold from the right of this view to satisfy `predicate`, like [`find_right`](FoldListSlice::find_right).
+    ///
+    /// Returns its index in this view (from the left), a reference to it, and the fold of every element of this view after it, or [`None`] if the fold of this whole view doesn't satisfy `predicate`. This is *O*(N + log(n)).
+    pub fn find_right(&self, predicate: impl Fn(&D)->bool) -> Option<(usize, &'a T, D)> {
+        let settings = self.list.get_settings();
+        let len = self.len();
+        let ((first_chunk, first_offset, first), (last_chunk, last_offset, last)) = self.ends()?;
+        let mut post = settings.empty();
+        if first_chunk == last_chunk {
+            return Self::scan_right(&settings, &first[first_offset..=last_offset], len, &mut post, &predicate);
+        }
+        if let Some(found) = Self::scan_right(&settings, &last[..=last_offset], len, &mut post, &predicate) {
+            return Some(found);
+        }
+        let after = len - (last_offset + 1);
+        let middle = self.list.underlying.view_range(first_chunk + 1..last_chunk);
+        let Some((i, _, (n, d))) = middle.find_right(|(_,d)| predicate(&settings.op(d.clone(), post.clone()))) else {
+            post = settings.op(middle.fold().1, post);
+            return Self::scan_right(&settings, &first[first_offset..], first.len - first_offset, &mut post, &predicate);
+        };
+        let chunk = self.list.underlying.get(first_chunk + 1 + i);
+        let mut post = settings.op(d, post);
+        let (first, tail) = chunk.split_first().unwrap();
+        Self::scan_right(&settings, tail, after - n, &mut post, &predicate)
+            .or_else(|| Some((after - n - chunk.len, first, post)))
+    }
+
+    /// Restrict this view to its `n` leftmost elements, or do nothing if it has `n` or fewer.
+    pub fn view_take_left(self, n: usize) -> Self {
+        Self { end: self.start + n.min(self.len()), ..self }
+    }
+
+    /// Restrict this view to its `n` rightmost elements, or do nothing if it has `n` or fewer.
+    pub fn view_take_right(self, n: usize) -> Self {
+        Self { start: self.end - n.min(self.len()), ..self }
+    }
+
+    /// Contract this view by `n` elements on the left, or to nothing if it has `n` or fewer.
+    pub fn view_drop_left(self, n: usize) -> Self {
+        Self { start: self.start + n.min(self.len()), ..self }
+    }
+
+    /// Contract this view by `n` elements on the right, or to nothing if it has `n` or fewer.
+    pub fn view_drop_right(self, n: usize) -> Self {
+        Self { end: self.end - n.min(self.len()), ..self }
+    }
+
+    /// Restrict this view to the elements at indices in `range`, like [`view_range`](FoldListSlice::view_range).
+    ///
+    /// Panics if `range` is out of bounds, or if it starts after it ends.
+    pub fn view_range(self, range: impl RangeBounds<usize>) -> Self {
+        let (start, end) = range_to_start_end(range, self.len());
+        Self { start: self.start + start, end: self.start + end, ..self }
+    }
+
+    /// Like [`view_range`](ChunkedFoldListSlice::view_range), but returns [`None`] instead of panicking.
+    pub fn try_view_range(self, range: impl RangeBounds<usize>) -> Option<Self> {
+        let (start, end) = try_range_to_start_end(range, self.len())?;
+        Some(Self { start: self.start + start, end: self.start + end, ..self })
+    }
+
+    /// Restrict this view to the longest range that starts on the left and whose fold doesn't meet `predicate`, like [`view_take_left_until`](FoldListSlice::view_take_left_until).
+    pub fn view_take_left_until(self, predicate: impl Fn(&D)->bool) -> Self {
+        let n = self.find_left(predicate).map_or(self.len(), |(i,_,_)| i);
+        self.view_take_left(n)
+    }
+
+    /// Contract this view on the left while the to-be-discarded range's fold doesn't meet `predicate`, like [`view_drop_left_until`](FoldListSlice::view_drop_left_until).
+    pub fn view_drop_left_until(self, predicate: impl Fn(&D)->bool) -> Self {
+        let n = self.find_left(predicate).map_or(self.len(), |(i,_,_)| i);
+        self.view_drop_left(n)
+    }
+
+    /// Restrict this view to the longest range that starts on the right and whose fold doesn't meet `predicate`, like [`view_take_right_until`](FoldListSlice::view_take_right_until).
+    pub fn view_take_right_until(self, predicate: impl Fn(&D)->bool) -> Self {
+        let n = self.find_right(predicate).map_or(self.len(), |(i,_,_)| self.len() - 1 - i);
+        self.view_take_right(n)
+    }
+
+    /// Contract this view on the right while the to-be-discarded range's fold doesn't meet `predicate`, like [`view_drop_right_until`](FoldListSlice::view_drop_right_until).
+    pub fn view_drop_right_until(self, predicate: impl Fn(&D)->bool) -> Self {
+        let n = self.find_right(predicate).map_or(self.len(), |(i,_,_)| self.len() - 1 - i);
+        self.view_drop_right(n)
+    }
+}
+
+impl<'a, T: core::fmt::Debug, D: Clone, Settings: FoldSettings<T,D>, const N: usize> core::fmt::Debug for ChunkedFoldListSlice<'a, T, D, Settings, N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T, D: Clone, Settings: FoldSettings<T,D>, const N: usize> IntoIterator for ChunkedFoldListSlice<'a, T, D, Settings, N> {
+    type Item = &'a T;
+    type IntoIter = core::iter::Take<core::iter::Skip<core::iter::Flatten<Iter<'a, False, Chunk<T,D,N>, (usize,(usize,D))>>>>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
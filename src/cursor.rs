@@ -0,0 +1,276 @@
+use core::{marker::PhantomData, ptr::NonNull};
+
+use crate::{fold_chain::{FoldChain, WAVLNode}, fold_list::FoldList, fold_settings::{FoldSettings, SettingsWithSize}};
+
+type Chain<T,D,Settings> = FoldChain<T,(usize,D),SettingsWithSize<Settings>>;
+type NodePtr<T,D> = NonNull<WAVLNode<T,(usize,D)>>;
+
+/// A cursor which points at an element of a [`FoldList`], and can be moved to its neighbors in amortized *O*(1).
+///
+/// Like [`alloc::collections::linked_list::Cursor`], a cursor may also point at the "ghost" position, one past the rightmost element,
+/// whose index is the length of the list, and which has no [`current`](Cursor::current) element.
+/// Moving right from the ghost position wraps around to the leftmost element, and vice versa.
+///
+/// Create one with [`FoldList::cursor_front`], [`FoldList::cursor_back`] or [`FoldList::cursor_at`].
+pub struct Cursor<'a,T,D: Clone,Settings: FoldSettings<T,D>> {
+    list: &'a FoldList<T,D,Settings>,
+    node: Option<NodePtr<T,D>>,
+    index: usize,
+}
+
+/// A cursor which points at an element of a [`FoldList`], like [`Cursor`], but which can also edit the list around it.
+///
+/// Replacing the current element doesn't update the list's folds right away; that's done once the cursor moves, is dropped, or is asked for a fold.
+/// This makes repeatedly editing the same element cheap.
+///
+/// Create one with [`FoldList::cursor_front_mut`], [`FoldList::cursor_back_mut`] or [`FoldList::cursor_at_mut`].
+pub struct CursorMut<'a,T,D: Clone,Settings: FoldSettings<T,D>> {
+    list: NonNull<FoldList<T,D,Settings>>,
+    node: Option<NodePtr<T,D>>,
+    index: usize,
+    //whether the current node's value changed since the folds were last updated
+    is_dirty: bool,
+    _m: PhantomData<&'a mut FoldList<T,D,Settings>>,
+}
+
+//send and sync are justified because the node pointers always point into the list, which is borrowed for 'a
+unsafe impl<'a, T: Sync, D: Clone + Sync, Settings: FoldSettings<T,D> + Sync> Sync for Cursor<'a, T, D, Settings> {}
+unsafe impl<'a, T: Sync, D: Clone + Sync, Settings: FoldSettings<T,D> + Sync> Send for Cursor<'a, T, D, Settings> {}
+unsafe impl<'a, T: Sync, D: Clone + Sync, Settings: FoldSettings<T,D> + Sync> Sync for CursorMut<'a, T, D, Settings> {}
+unsafe impl<'a, T: Send, D: Clone + Send, Settings: FoldSettings<T,D> + Send> Send for CursorMut<'a, T, D, Settings> {}
+
+impl<'a, T, D: Clone, Settings: FoldSettings<T,D>> Clone for Cursor<'a, T, D, Settings> {
+    fn clone(&self) -> Self {
+        Self { list: self.list, node: self.node, index: self.index }
+    }
+}
+
+impl<'a, T, D: Clone, Settings: FoldSettings<T,D>> Cursor<'a, T, D, Settings> {
+    pub(crate) fn new_at(list: &'a FoldList<T,D,Settings>, index: usize) -> Self {
+        let len = list.len();
+        if index > len {
+            panic!("Index out of bounds: the index is {} but the length is {}",index,len);
+        }
+        Self { list, node: list.underlying.cursor_node_at_index(index), index }
+    }
+
+    /// Get the index of the current element, or the length of the list if this cursor is at the ghost position.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Get a reference to the current element, or [`None`] if this cursor is at the ghost position.
+    pub fn current(&self) -> Option<&'a T> {
+        self.node.map(|node| unsafe { Chain::<T,D,Settings>::cursor_value(node) })
+    }
+
+    /// Move to the next element to the right, or to the ghost position if this is the rightmost element, or to the leftmost element if this is the ghost position.
+    pub fn move_next(&mut self) {
+        match self.node {
+            Some(node) => {
+                self.node = unsafe { Chain::<T,D,Settings>::cursor_next_node(node) };
+                self.index += 1;
+            },
+            None => {
+                self.node = self.list.underlying.cursor_leftmost_node();
+                self.index = 0;
+            },
+        }
+    }
+
+    /// Move to the next element to the left, or to the ghost position if this is the leftmost element, or to the rightmost element if this is the ghost position.
+    pub fn move_prev(&mut self) {
+        match self.node {
+            Some(node) => {
+                self.node = unsafe { Chain::<T,D,Settings>::cursor_prev_node(node) };
+                self.index = self.index.checked_sub(1).unwrap_or(self.list.len());
+            },
+            None => {
+                self.node = self.list.underlying.cursor_rightmost_node();
+                self.index = self.index.saturating_sub(1);
+            },
+        }
+    }
+
+    /// Get the fold of every element left of the current one (or of the whole list, at the ghost position), in *O*(log(n)).
+    pub fn prefix_fold(&self) -> D {
+        unsafe { self.list.underlying.cursor_fold_before(self.node).1 }
+    }
+}
+
+impl<'a, T, D: Clone, Settings: FoldSettings<T,D>> CursorMut<'a, T, D, Settings> {
+    pub(crate) fn new_at(list: &'a mut FoldList<T,D,Settings>, index: usize) -> Self {
+        let len = list.len();
+        if index > len {
+            panic!("Index out of bounds: the index is {} but the length is {}",index,len);
+        }
+        let node = list.underlying.cursor_node_at_index(index);
+        Self { list: NonNull::from(list), node, index, is_dirty: false, _m: PhantomData }
+    }
+
+    fn chain(&self) -> &Chain<T,D,Settings> {
+        unsafe { &self.list.as_ref().underlying }
+    }
+
+    fn chain_mut(&mut self) -> &mut Chain<T,D,Settings> {
+        unsafe { &mut self.list.as_mut().underlying }
+    }
+
+    //brings the folds up to date with the current element
+    fn flush(&mut self) {
+        if !core::mem::take(&mut self.is_dirty) {return}
+        let Some(node) = self.node else {return};
+        unsafe { self.chain_mut().cursor_recalc_fold_from(node) }
+    }
+
+    /// Get the index of the current element, or the length of the list if this cursor is at the ghost position.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Get a reference to the current element, or [`None`] if this cursor is at the ghost position.
+    pub fn current(&self) -> Option<&T> {
+        self.node.map(|node| unsafe { Chain::<T,D,Settings>::cursor_value(node) })
+    }
+
+    /// Get an immutable [`Cursor`] at the same position as this one, which can't outlive it.
+    pub fn as_cursor(&mut self) -> Cursor<'_,T,D,Settings> {
+        self.flush();
+        Cursor { list: unsafe { self.list.as_ref() }, node: self.node, index: self.index }
+    }
+
+    /// Move to the next element to the right. See [`Cursor::move_next`].
+    pub fn move_next(&mut self) {
+        self.flush();
+        match self.node {
+            Some(node) => {
+                self.node = unsafe { Chain::<T,D,Settings>::cursor_next_node(node) };
+                self.index += 1;
+            },
+            None => {
+                self.node = self.chain().cursor_leftmost_node();
+                self.index = 0;
+            },
+        }
+    }
+
+    /// Move to the next element to the left. See [`Cursor::move_prev`].
+    pub fn move_prev(&mut self) {
+        self.flush();
+        match self.node {
+            Some(node) => {
+                self.node = unsafe { Chain::<T,D,Settings>::cursor_prev_node(node) };
+                self.index = self.index.checked_sub(1).unwrap_or(unsafe { self.list.as_ref() }.len());
+            },
+            None => {
+                self.node = self.chain().cursor_rightmost_node();
+                self.index = self.index.saturating_sub(1);
+            },
+        }
+    }
+
+    /// Get the fold of every element left of the current one (or of the whole list, at the ghost position), in *O*(log(n)).
+    pub fn prefix_fold(&mut self) -> D {
+        self.flush();
+        unsafe { self.chain().cursor_fold_before(self.node).1 }
+    }
+
+    /// Replace the current element with `value`.
+    ///
+    /// Returns `Ok(prev)`, where `prev` is the previous element, or `Err(value)` if this cursor is at the ghost position.
+    pub fn replace_current(&mut self, value: T) -> Result<T,T> {
+        let Some(node) = self.node else {return Err(value)};
+        self.is_dirty = true;
+        Ok(core::mem::replace(unsafe { Chain::<T,D,Settings>::cursor_value_mut(node) }, value))
+    }
+
+    /// Mutate the current element via a closure, and return the result of the closure.
+    ///
+    /// If this cursor is at the ghost position, the input of the closure will be [`None`].
+    pub fn update_current<R>(&mut self, f: impl FnOnce(Option<&mut T>) -> R) -> R {
+        let Some(node) = self.node else {return f(None)};
+        self.is_dirty = true;
+        f(Some(unsafe { Chain::<T,D,Settings>::cursor_value_mut(node) }))
+    }
+
+    /// Insert `value` directly left of the current element (or at the right end of the list, at the ghost position).
+    ///
+    /// The cursor keeps pointing at the same element, whose index goes up by one.
+    pub fn insert_before(&mut self, value: T) {
+        self.flush();
+        let node = self.node;
+        unsafe { self.chain_mut().cursor_insert_before(node, value); }
+        self.index += 1;
+    }
+
+    /// Insert `value` directly right of the current element (or at the left end of the list, at the ghost position).
+    ///
+    /// The cursor keeps pointing at the same element.
+    pub fn insert_after(&mut self, value: T) {
+        self.flush();
+        let next = match self.node {
+            Some(node) => unsafe { Chain::<T,D,Settings>::cursor_next_node(node) },
+            None => self.chain().cursor_leftmost_node(),
+        };
+        unsafe { self.chain_mut().cursor_insert_before(next, value); }
+        if self.node.is_none() {
+            self.index += 1;
+        }
+    }
+
+    /// Remove and return the current element, and move the cursor to the element which was right of it (or to the ghost position).
+    ///
+    /// Returns [`None`] and does nothing if this cursor is at the ghost position.
+    pub fn remove_current(&mut self) -> Option<T> {
+        self.flush();
+        let node = self.node?;
+        self.node = unsafe { Chain::<T,D,Settings>::cursor_next_node(node) };
+        Some(unsafe { self.chain_mut().cursor_remove(node) })
+    }
+}
+
+impl<'a, T, D: Clone, Settings: FoldSettings<T,D>> Drop for CursorMut<'a, T, D, Settings> {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+/// A mutable reference to an element of a [`FoldList`], which updates the list's folds when dropped, like [`alloc::collections::binary_heap::PeekMut`].
+/// 
+/// Create one with [`FoldList::get_mut`] or [`FoldList::try_get_mut`].
+pub struct ElementMut<'a,T,D: Clone,Settings: FoldSettings<T,D>> {
+    //always points at an element, never at the ghost position
+    cursor: CursorMut<'a,T,D,Settings>,
+}
+
+impl<'a, T, D: Clone, Settings: FoldSettings<T,D>> ElementMut<'a, T, D, Settings> {
+    pub(crate) fn new_at(list: &'a mut FoldList<T,D,Settings>, index: usize) -> Self {
+        Self { cursor: CursorMut::new_at(list, index) }
+    }
+
+    /// Get the index of this element.
+    pub fn index(&self) -> usize {
+        self.cursor.index
+    }
+
+    /// Turn this into a [`CursorMut`] pointing at this element.
+    pub fn into_cursor(self) -> CursorMut<'a,T,D,Settings> {
+        self.cursor
+    }
+}
+
+impl<'a, T, D: Clone, Settings: FoldSettings<T,D>> core::ops::Deref for ElementMut<'a, T, D, Settings> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        let Some(node) = self.cursor.node else {unreachable!()};
+        unsafe { Chain::<T,D,Settings>::cursor_value(node) }
+    }
+}
+
+impl<'a, T, D: Clone, Settings: FoldSettings<T,D>> core::ops::DerefMut for ElementMut<'a, T, D, Settings> {
+    fn deref_mut(&mut self) -> &mut T {
+        let Some(node) = self.cursor.node else {unreachable!()};
+        self.cursor.is_dirty = true;
+        unsafe { Chain::<T,D,Settings>::cursor_value_mut(node) }
+    }
+}
@@ -1,7 +1,13 @@
 use core::panic;
 use core::{iter::FusedIterator, marker::PhantomData, ptr::NonNull};
-
-use crate::fold_settings::SettingsWithSize;
+use alloc::{boxed::Box, vec::Vec};
+#[cfg(feature = "handles")]
+use alloc::sync::{Arc, Weak};
+
+use crate::fold_settings::{InvertibleFoldSettings, SettingsWithSize};
+#[cfg(feature = "handles")]
+use crate::handle::HandleMarker;
+use crate::node_pool::NodePool;
 use crate::misc::private::Sealed;
 use crate::misc::{NoneFun, OptOpFun, SingleEndedRange, SomeFun, TupleFun};
 use crate::{fold_list::{FoldList}, fold_settings::{FoldSettings, FoldSettingsStruct}, fold_simplification::{FoldSimplification, SimplificationWithShortcut, SimplificationWithoutShortcut}, misc::{bool_assert_into, bool_ifelse_clone, cswap, Bool, EmptyFn, False, Fun, True}};
@@ -15,17 +21,23 @@ pub(crate) struct WAVLNode<T,D> {
     right: Option<Box<WAVLNode<T,D>>>,
     is_right_child: bool,
     parent_ptr: Option<NonNull<WAVLNode<T,D>>>,
-    _pin: std::marker::PhantomPinned
+    //only set once a handle to this node is made, and dropped along with the node, which is how handles know that it's gone; see crate::handle
+    #[cfg(feature = "handles")]
+    handle_marker: Option<Arc<HandleMarker>>,
+    //the number of nodes in this subtree with handle markers, kept up to date along with delta_whole, so that moving nodes between chains only visits the subtrees which have some
+    #[cfg(feature = "handles")]
+    marked_count: usize,
+    _pin: core::marker::PhantomPinned
 }
 
 impl<T,D: Clone> WAVLNode<T,D> {
     fn new_leaf_unboxed<P: FoldSettings<T,D>>(p: P, value: T) -> WAVLNode<T,D> {
         let d = p.delta_of(&value);
-        Self { parent_ptr: None, value, rank: 0, delta_whole: d, left: None, right: None, is_right_child: false, _pin: std::marker::PhantomPinned }
+        Self { parent_ptr: None, value, rank: 0, delta_whole: d, left: None, right: None, is_right_child: false, #[cfg(feature = "handles")] handle_marker: None, #[cfg(feature = "handles")] marked_count: 0, _pin: core::marker::PhantomPinned }
     }
 
-    fn new_leaf< P: FoldSettings<T,D>>(p: P, value: T) -> Option<Box<WAVLNode<T,D>>> {
-        let ret = Box::new(Self::new_leaf_unboxed(p, value));
+    fn new_leaf< P: FoldSettings<T,D>>(p: P, value: T, pool: &mut NodePool<T,D>) -> Option<Box<WAVLNode<T,D>>> {
+        let ret = pool.alloc(Self::new_leaf_unboxed(p, value));
         Some(ret)
     }
 
@@ -46,8 +58,15 @@ impl<T,D: Clone> WAVLNode<T,D> {
             p.op(d1,r.delta_whole.clone())
         } else {d1};
         self.delta_whole = d2;
+        #[cfg(feature = "handles")]
+        self.recalc_marked_count();
     }
     
+    #[cfg(feature = "handles")]
+    fn recalc_marked_count(&mut self) {
+        self.marked_count = self.handle_marker.is_some() as usize + self.left.as_ref().map_or(0, |l| l.marked_count) + self.right.as_ref().map_or(0, |r| r.marked_count);
+    }
+
     fn inform_children_and_recalc<P: FoldSettings<T,D>>(&mut self,p: P) {
         let self_ptr: NonNull<WAVLNode<T,D>> = self.into();
         let d0 = p.delta_of(&self.value);
@@ -64,6 +83,8 @@ impl<T,D: Clone> WAVLNode<T,D> {
             p.op(d1,right.delta_whole.clone())
         } else {d1};
         self.delta_whole = d2;
+        #[cfg(feature = "handles")]
+        self.recalc_marked_count();
     }
 
     fn update_and_rebalance_continues(self_opt: &mut Option<Box<Self>>, settings: impl FoldSettings<T,D>) -> bool {
@@ -346,6 +367,13 @@ impl<T,D: Clone> WAVLNode<T,D> {
         }
         check_child(self, &self.left);
         check_child(self, &self.right);
+        #[cfg(feature = "handles")]
+        {
+            let expected = self.handle_marker.is_some() as usize + self.left.as_ref().map_or(0, |l| l.marked_count) + self.right.as_ref().map_or(0, |r| r.marked_count);
+            if self.marked_count != expected {
+                panic!("Expected {} marked nodes in this subtree, actual: {}", expected, self.marked_count);
+            }
+        }
         true
     }
 
@@ -422,14 +450,14 @@ impl<T,D: Clone> WAVLNode<T,D> {
         ret
     }
 
-    fn push_left_and_get_address_of_node_with_new_element_template<Reversed: Bool>(this_opt: &mut Option<Box<Self>>, settings: impl FoldSettings<T,D>, value: T) -> NonNull<WAVLNode<T,D>> {
+    fn push_left_and_get_address_of_node_with_new_element_template<Reversed: Bool>(this_opt: &mut Option<Box<Self>>, settings: impl FoldSettings<T,D>, value: T, pool: &mut NodePool<T,D>) -> NonNull<WAVLNode<T,D>> {
         let Some(this) = this_opt else {
-            let new = Self::new_leaf(settings, value);
+            let new = Self::new_leaf(settings, value, pool);
             let ret = NonNull::from(new.as_ref().unwrap().as_ref());
             *this_opt = new;
             return ret
         };
-        let ret = Self::push_left_and_get_address_of_node_with_new_element_template::<Reversed>(this.left_child_template_mut::<Reversed>(), settings, value);
+        let ret = Self::push_left_and_get_address_of_node_with_new_element_template::<Reversed>(this.left_child_template_mut::<Reversed>(), settings, value, pool);
         Self::update_and_rebalance_continues(this_opt, settings);
         return ret
     }
@@ -528,23 +556,23 @@ impl<T,D: Clone> WAVLNode<T,D> {
         }
     }
 
-    fn new_from_iterator_left_to_right_template<Reversed: Bool>(mut iter: impl Iterator<Item=T>, settings: impl FoldSettings<T,D>) -> Option<Box<Self>> {
-        fn with_height_estimate<Reversed: Bool,T,D: Clone>(height_est: usize, iter: &mut impl Iterator<Item=T>, settings: impl FoldSettings<T,D>) -> Result<Box<WAVLNode<T,D>>,Option<Box<WAVLNode<T,D>>>> {
+    fn new_from_iterator_left_to_right_template<Reversed: Bool>(mut iter: impl Iterator<Item=T>, settings: impl FoldSettings<T,D>, pool: &mut NodePool<T,D>) -> Option<Box<Self>> {
+        fn with_height_estimate<Reversed: Bool,T,D: Clone>(height_est: usize, iter: &mut impl Iterator<Item=T>, settings: impl FoldSettings<T,D>, pool: &mut NodePool<T,D>) -> Result<Box<WAVLNode<T,D>>,Option<Box<WAVLNode<T,D>>>> {
             if height_est == 0 {
                 let Some(value) = iter.next() else {
                     return Err(None)
                 };
-                return Ok(WAVLNode::new_leaf(settings, value).unwrap())
+                return Ok(WAVLNode::new_leaf(settings, value, pool).unwrap())
             }
-            let left = with_height_estimate::<Reversed,_,_>(height_est-1, iter, settings)?;
+            let left = with_height_estimate::<Reversed,_,_>(height_est-1, iter, settings, pool)?;
             let Some(parent) = iter.next() else {
                 return Err(Some(left))
             };
-            let right = match with_height_estimate::<Reversed,_,_>(height_est - 1, iter, settings) {
+            let right = match with_height_estimate::<Reversed,_,_>(height_est - 1, iter, settings, pool) {
                 Ok(right) => right,
                 Err(right_opt) => {
                     let mut ret = Some(left);
-                    WAVLNode::push_left_and_get_address_of_node_with_new_element_template::<Reversed::Not>(&mut ret, settings, parent);
+                    WAVLNode::push_left_and_get_address_of_node_with_new_element_template::<Reversed::Not>(&mut ret, settings, parent, pool);
                     WAVLNode::append_tree_right_opt_template::<Reversed>(&mut ret, right_opt, settings);
                     return Err(ret)
                 },
@@ -558,7 +586,7 @@ impl<T,D: Clone> WAVLNode<T,D> {
                 )
             );
             let rank = ((height_est * 3) / 2) as u8;
-            let mut ret = Box::new(WAVLNode {
+            let mut ret = pool.alloc(WAVLNode {
                 parent_ptr: None,
                 delta_whole,
                 is_right_child: false,
@@ -566,7 +594,11 @@ impl<T,D: Clone> WAVLNode<T,D> {
                 value: parent,
                 left: None,
                 right: None,
-                _pin: std::marker::PhantomPinned,
+                #[cfg(feature = "handles")]
+                handle_marker: None,
+                #[cfg(feature = "handles")]
+                marked_count: 0,
+                _pin: core::marker::PhantomPinned,
             });
             right.is_right_child = true;
             left.parent_ptr = Some(ret.as_mut().into());
@@ -575,9 +607,9 @@ impl<T,D: Clone> WAVLNode<T,D> {
             ret.right = Some(right);
             Ok(ret)
         }
-        fn inc_height<Reversed: Bool,T,D: Clone>(node: Box<WAVLNode<T,D>>, cur_height: usize, iter: &mut impl Iterator<Item=T>, settings: impl FoldSettings<T,D>) -> Result<Box<WAVLNode<T,D>>,Box<WAVLNode<T,D>>> {
+        fn inc_height<Reversed: Bool,T,D: Clone>(node: Box<WAVLNode<T,D>>, cur_height: usize, iter: &mut impl Iterator<Item=T>, settings: impl FoldSettings<T,D>, pool: &mut NodePool<T,D>) -> Result<Box<WAVLNode<T,D>>,Box<WAVLNode<T,D>>> {
             let Some(parent) = iter.next() else {return Err(node)};
-            match with_height_estimate::<Reversed,_,_>(cur_height, iter, settings) {
+            match with_height_estimate::<Reversed,_,_>(cur_height, iter, settings, pool) {
                 Ok(right) => {
                     let (mut left,mut right) = cswap::<Reversed,_>(node,right);
                     let delta_whole = settings.op(
@@ -588,7 +620,7 @@ impl<T,D: Clone> WAVLNode<T,D> {
                         )
                     );
                     let rank = (((cur_height + 1) * 3) / 2) as u8;
-                    let mut ret = Box::new(WAVLNode {
+                    let mut ret = pool.alloc(WAVLNode {
                         parent_ptr: None,
                         delta_whole,
                         is_right_child: false,
@@ -596,7 +628,11 @@ impl<T,D: Clone> WAVLNode<T,D> {
                         value: parent,
                         left: None,
                         right: None,
-                        _pin: std::marker::PhantomPinned,
+                        #[cfg(feature = "handles")]
+                        handle_marker: None,
+                        #[cfg(feature = "handles")]
+                        marked_count: 0,
+                        _pin: core::marker::PhantomPinned,
                     });
                     right.is_right_child = true;
                     left.parent_ptr = Some(ret.as_mut().into());
@@ -607,7 +643,7 @@ impl<T,D: Clone> WAVLNode<T,D> {
                 },
                 Err(right) => {
                     let mut node_opt = Some(node);
-                    WAVLNode::push_left_and_get_address_of_node_with_new_element_template::<Reversed::Not>(&mut node_opt, settings, parent);
+                    WAVLNode::push_left_and_get_address_of_node_with_new_element_template::<Reversed::Not>(&mut node_opt, settings, parent, pool);
                     WAVLNode::append_tree_right_opt_template::<Reversed>(&mut node_opt, right, settings);
                     Err(node_opt.unwrap())
                 }
@@ -615,12 +651,12 @@ impl<T,D: Clone> WAVLNode<T,D> {
         }
         let min_size_hint = iter.size_hint().0;
         let mut cur_height = ((min_size_hint | 2) - 1).ilog2() as usize;
-        let mut ret = match with_height_estimate::<Reversed,_,_>(cur_height, &mut iter, settings) {
+        let mut ret = match with_height_estimate::<Reversed,_,_>(cur_height, &mut iter, settings, pool) {
             Ok(r) => r,
             Err(r) => return r,
         };
         loop {
-            ret = match inc_height::<Reversed,_,_>(ret, cur_height, &mut iter, settings) {
+            ret = match inc_height::<Reversed,_,_>(ret, cur_height, &mut iter, settings, pool) {
                 Ok(r) => {
                     cur_height += 1;
                     r
@@ -635,6 +671,8 @@ impl<T,D: Clone> WAVLNode<T,D> {
         let right = core::mem::take(&mut self.right);
         self.rank = 0;
         self.delta_whole = settings.delta_of(&self.value);
+        #[cfg(feature = "handles")]
+        self.recalc_marked_count();
         (left,right)
     }
 
@@ -721,7 +759,11 @@ impl<T: Clone, D: Clone> WAVLNode<T,D> {
             right: self.right.as_ref().map(|l| l.as_ref().clone_boxed()),
             is_right_child: self.is_right_child,
             parent_ptr: None,
-            _pin: std::marker::PhantomPinned,
+            #[cfg(feature = "handles")]
+            handle_marker: None,
+            #[cfg(feature = "handles")]
+            marked_count: 0,
+            _pin: core::marker::PhantomPinned,
         });
         let ret_ptr = NonNull::from(ret.as_ref());
         if let Some(l) = &mut ret.left {
@@ -734,6 +776,31 @@ impl<T: Clone, D: Clone> WAVLNode<T,D> {
     }
 }
 
+impl<T, D: Clone> WAVLNode<T,D> {
+    //the fold of every element of the tree containing node which is left of it, found by walking up to the root
+    unsafe fn fold_before<Settings: FoldSettings<T,D>>(node: NonNull<Self>, settings: Settings) -> D {
+        unsafe {
+            let mut acc = node.as_ref().left.as_ref().map(|l| l.delta_whole.clone());
+            let mut cur = node.as_ref();
+            while let Some(parent) = cur.parent_ptr {
+                let parent = parent.as_ref();
+                if cur.is_right_child {
+                    let mut d = settings.delta_of(&parent.value);
+                    if let Some(l) = &parent.left {
+                        d = settings.op(l.delta_whole.clone(), d);
+                    }
+                    acc = Some(match acc {
+                        None => d,
+                        Some(a) => settings.op(d, a),
+                    });
+                }
+                cur = parent;
+            }
+            acc.unwrap_or_else(|| settings.empty())
+        }
+    }
+}
+
 unsafe fn bubble_up_fold_from_node<T, D: Clone>(mut node: NonNull<WAVLNode<T,D>>, settings: impl FoldSettings<T,D>) {
     unsafe {
         let node_mut = node.as_mut();
@@ -744,6 +811,24 @@ unsafe fn bubble_up_fold_from_node<T, D: Clone>(mut node: NonNull<WAVLNode<T,D>>
     }
 }
 
+//recalculates the folds from node up to the root when dropped, so that they stay right even if a closure mutating node's value panics
+struct BubbleUpFoldGuard<T, D: Clone, Settings: FoldSettings<T,D>> {
+    node: NonNull<WAVLNode<T,D>>,
+    settings: Settings,
+}
+
+impl<T, D: Clone, Settings: FoldSettings<T,D>> Drop for BubbleUpFoldGuard<T, D, Settings> {
+    fn drop(&mut self) {
+        unsafe { bubble_up_fold_from_node(self.node, self.settings) }
+    }
+}
+
+//mutates node's value via f, and then recalculates the folds above it, whether or not f panics
+unsafe fn update_node_value<T, D: Clone, R>(node: NonNull<WAVLNode<T,D>>, settings: impl FoldSettings<T,D>, f: impl FnOnce(&mut T) -> R) -> R {
+    let _guard = BubbleUpFoldGuard { node, settings };
+    f(unsafe { &mut (*node.as_ptr()).value })
+}
+
 unsafe fn bubble_up_rebalance_from_node<T, D: Clone>(node: NonNull<WAVLNode<T,D>>, mut root: NonNull<Option<Box<WAVLNode<T,D>>>>, settings: impl FoldSettings<T,D>) {
     unsafe {
         let Some(mut parent) = node.as_ref().parent_ptr else {
@@ -1396,12 +1481,15 @@ pub trait MutFoldChainSlice<'a,T: 'a,D: Clone + 'a> : FoldChainSlice<'a,T,D> + S
     /// This is faster than [`append_left`](MutFoldChainSlice::append_left)ing them one-by-one, but not asymptotically faster.
     fn append_left_from_iter(&mut self, iter: impl Iterator<Item=T>) {
         let settings = self.get_settings();
-        let to_add = WAVLNode::new_from_iterator_left_to_right_template::<<Self::IsReversed as Bool>::Not>(iter, settings);
+        let to_add = WAVLNode::new_from_iterator_left_to_right_template::<<Self::IsReversed as Bool>::Not>(iter, settings, self.borrow_mut().pool_mut());
         self.append_all_left(FoldChain {
             leftmost_node_ptr: to_add.as_ref().map(|t| t.all_the_way_left_template::<False>().into()),
             rightmost_node_ptr: to_add.as_ref().map(|t| t.all_the_way_left_template::<True>().into()),
             root: to_add,
             settings: settings,
+            pool: NodePool::non_keeping(),
+            #[cfg(feature = "handles")]
+            handle_token: None,
         });
     }
 
@@ -1410,12 +1498,15 @@ pub trait MutFoldChainSlice<'a,T: 'a,D: Clone + 'a> : FoldChainSlice<'a,T,D> + S
     /// This is faster than [`append_right`](MutFoldChainSlice::append_right)ing them one-by-one, but not asymptotically faster.
     fn append_right_from_iter(&mut self, iter: impl Iterator<Item=T>) {
         let settings = self.get_settings();
-        let to_add = WAVLNode::new_from_iterator_left_to_right_template::<Self::IsReversed>(iter, settings);
+        let to_add = WAVLNode::new_from_iterator_left_to_right_template::<Self::IsReversed>(iter, settings, self.borrow_mut().pool_mut());
         self.append_all_right(FoldChain {
             leftmost_node_ptr: to_add.as_ref().map(|t| t.all_the_way_left_template::<False>().into()),
             rightmost_node_ptr: to_add.as_ref().map(|t| t.all_the_way_left_template::<True>().into()),
             root: to_add,
             settings: settings,
+            pool: NodePool::non_keeping(),
+            #[cfg(feature = "handles")]
+            handle_token: None,
         });
     }
 }
@@ -1426,7 +1517,12 @@ pub struct FoldChain<T,D: Clone, Settings: FoldSettings<T,D>> {
     pub(crate) leftmost_node_ptr: Option<NonNull<WAVLNode<T,D>>>,
     pub(crate) rightmost_node_ptr: Option<NonNull<WAVLNode<T,D>>>,
     //the above pointers should never be none unless root is none
-    pub(crate) settings: Settings
+    pub(crate) settings: Settings,
+    pub(crate) pool: NodePool<T,D>,
+    //the token which the handle markers of this chain's nodes point to; see crate::handle.
+    //no two chains share a token, and it's none until a handle to one of this chain's elements is made or moved in
+    #[cfg(feature = "handles")]
+    pub(crate) handle_token: Option<Arc<()>>,
 }
 
 impl<T, D: Clone, Settings: FoldSettings<T,D>> Sealed for &FoldChain<T, D, Settings> {}
@@ -1494,7 +1590,63 @@ impl<T, D: Clone, Settings: FoldSettings<T,D>> FoldChain<T, D, Settings> {
             leftmost_node_ptr: None,
             rightmost_node_ptr: None,
             settings,
+            pool: NodePool::non_keeping(),
+            #[cfg(feature = "handles")]
+            handle_token: None,
+        }
+    }
+
+    /// Create a new empty `FoldChain`, with the specified [Settings](crate#fold-settings), which takes the allocations for new elements from `pool`,
+    /// and keeps the allocations of removed elements in it; see [`NodePool`].
+    pub fn from_settings_and_pool(settings: Settings, pool: NodePool<T,D>) -> Self {
+        Self {
+            root: None,
+            leftmost_node_ptr: None,
+            rightmost_node_ptr: None,
+            settings,
+            pool: pool.into_keeping(),
+            #[cfg(feature = "handles")]
+            handle_token: None,
+        }
+    }
+
+    /// Drop every element, and return this chain's [`NodePool`] with their allocations added to it, so that another chain can reuse them.
+    pub fn into_node_pool(mut self) -> NodePool<T,D> {
+        let mut pool = core::mem::take(&mut self.pool).into_keeping();
+        self.leftmost_node_ptr = None;
+        self.rightmost_node_ptr = None;
+        let mut stack: Vec<Box<WAVLNode<T,D>>> = self.root.take().into_iter().collect();
+        while let Some(mut node) = stack.pop() {
+            stack.extend(node.left.take());
+            stack.extend(node.right.take());
+            pool.free(node);
         }
+        pool
+    }
+
+    /// Get this chain's [`NodePool`], which holds spare allocations for new elements.
+    pub fn node_pool(&self) -> &NodePool<T,D> {
+        &self.pool
+    }
+
+    /// Get this chain's [`NodePool`] mutably, e.g. to [make spare allocations](NodePool::ensure_capacity) for new elements.
+    pub fn node_pool_mut(&mut self) -> &mut NodePool<T,D> {
+        &mut self.pool
+    }
+
+    /// Free the spare allocations in this chain's [`NodePool`].
+    pub fn shrink_to_fit(&mut self) {
+        self.pool.shrink_to_fit();
+    }
+
+    /// Split this chain in two at the first element which causes the fold from the left to satisfy `predicate`.
+    /// 
+    /// That element and everything to its right are removed and returned as a new `FoldChain`; the elements before it stay in `self`.
+    /// If no element causes the fold to satisfy `predicate`, the returned chain is empty.
+    /// 
+    /// `predicate` should be monotonic, as in [`mut_view_drop_left_until`](MutFoldChainSlice::mut_view_drop_left_until). This is *O*(log(n)).
+    pub fn split_off_where(&mut self, predicate: impl Fn(&D)->bool) -> FoldChain<T, D, Settings> {
+        self.mut_view_drop_left_until(predicate).take_all()
     }
 
     fn into_imm_template<'a>(&'a self) -> ImmFoldChainSliceStruct<'a,False,True,True,Settings,(),T,D> {
@@ -1740,6 +1892,368 @@ impl<T, D: Clone, Settings: FoldSettings<T,D>> FoldChain<T, D, Settings> {
 
 
 
+//node-level operations used by cursors; see crate::cursor
+//the node pointers passed to these must point into this chain
+impl<T, D: Clone, Settings: FoldSettings<T,D>> FoldChain<T, D, Settings> {
+    pub(crate) fn cursor_leftmost_node(&self) -> Option<NonNull<WAVLNode<T,D>>> {
+        self.leftmost_node_ptr
+    }
+
+    pub(crate) fn cursor_rightmost_node(&self) -> Option<NonNull<WAVLNode<T,D>>> {
+        self.rightmost_node_ptr
+    }
+
+    pub(crate) unsafe fn cursor_value<'b>(node: NonNull<WAVLNode<T,D>>) -> &'b T where T: 'b, D: 'b {
+        unsafe { &node.as_ref().value }
+    }
+
+    //the fold must be recalculated with cursor_recalc_fold_from afterwards
+    pub(crate) unsafe fn cursor_value_mut<'b>(mut node: NonNull<WAVLNode<T,D>>) -> &'b mut T where T: 'b, D: 'b {
+        unsafe { &mut node.as_mut().value }
+    }
+
+    pub(crate) unsafe fn cursor_next_node(node: NonNull<WAVLNode<T,D>>) -> Option<NonNull<WAVLNode<T,D>>> {
+        unsafe { node.as_ref().next_single_left_to_right_template::<False>() }
+    }
+
+    pub(crate) unsafe fn cursor_prev_node(node: NonNull<WAVLNode<T,D>>) -> Option<NonNull<WAVLNode<T,D>>> {
+        unsafe { node.as_ref().next_single_left_to_right_template::<True>() }
+    }
+
+    pub(crate) unsafe fn cursor_recalc_fold_from(&mut self, node: NonNull<WAVLNode<T,D>>) {
+        unsafe { bubble_up_fold_from_node(node, self.settings) }
+    }
+
+    //the fold of every element left of node, or of the whole chain if node is None
+    pub(crate) unsafe fn cursor_fold_before(&self, node: Option<NonNull<WAVLNode<T,D>>>) -> D {
+        unsafe {
+            let settings = self.settings;
+            let Some(node) = node else {
+                return self.root.as_ref().map_or_else(|| settings.empty(), |r| r.delta_whole.clone())
+            };
+            WAVLNode::fold_before(node, settings)
+        }
+    }
+
+    //inserts value directly left of node, or at the right end if node is None, and returns the new node
+    pub(crate) unsafe fn cursor_insert_before(&mut self, node: Option<NonNull<WAVLNode<T,D>>>, value: T) -> NonNull<WAVLNode<T,D>> {
+        unsafe {
+            let settings = self.settings;
+            let Some(rightmost) = self.rightmost_node_ptr else {
+                debug_assert!(node.is_none());
+                self.root = WAVLNode::new_leaf(settings, value, &mut self.pool);
+                let ret = NonNull::from(self.root.as_ref().unwrap().as_ref());
+                self.leftmost_node_ptr = Some(ret);
+                self.rightmost_node_ptr = Some(ret);
+                return ret
+            };
+            let (mut parent, is_right_child) = match node {
+                Some(n) => match &n.as_ref().left {
+                    None => (n, false),
+                    Some(l) => (NonNull::from(l.all_the_way_left_template::<True>()), true),
+                },
+                None => (rightmost, true),
+            };
+            let mut leaf = WAVLNode::new_leaf(settings, value, &mut self.pool);
+            let leaf_mut = leaf.as_mut().unwrap();
+            leaf_mut.parent_ptr = Some(parent);
+            leaf_mut.is_right_child = is_right_child;
+            let ret = NonNull::from(leaf_mut.as_ref());
+            if is_right_child {
+                parent.as_mut().right = leaf;
+            } else {
+                parent.as_mut().left = leaf;
+            }
+            bubble_up_rebalance_from_node(parent, NonNull::from(&mut self.root), settings);
+            if node.is_none() {
+                self.rightmost_node_ptr = Some(ret);
+            }
+            if node.is_some() && node == self.leftmost_node_ptr {
+                self.leftmost_node_ptr = Some(ret);
+            }
+            ret
+        }
+    }
+
+    pub(crate) unsafe fn cursor_remove(&mut self, node: NonNull<WAVLNode<T,D>>) -> T {
+        unsafe {
+            let settings = self.settings;
+            if Some(node) == self.leftmost_node_ptr {
+                self.leftmost_node_ptr = Self::cursor_next_node(node);
+            }
+            if Some(node) == self.rightmost_node_ptr {
+                self.rightmost_node_ptr = Self::cursor_prev_node(node);
+            }
+            let removed = WAVLNode::mutate_box_of_and_update_parents(node, NonNull::from(&mut self.root), settings, |box_mut| {
+                WAVLNode::pop_top_in_place_boxed(box_mut, settings)
+            });
+            let WAVLNode { value, .. } = self.pool.free(removed.unwrap());
+            value
+        }
+    }
+}
+
+//node-level operations used by handles; see crate::handle
+#[cfg(feature = "handles")]
+impl<T, D: Clone, Settings: FoldSettings<T,D>> FoldChain<T, D, Settings> {
+    //a marker which is dropped along with node's element, and is shared by every handle to it
+    //node must be in this chain
+    pub(crate) unsafe fn handle_marker_of(&mut self, node: NonNull<WAVLNode<T,D>>) -> Weak<HandleMarker> {
+        unsafe {
+            if node.as_ref().handle_marker.is_none() {
+                let token = self.handle_token.get_or_insert_with(|| Arc::new(()));
+                (*node.as_ptr()).handle_marker = Some(Arc::new(HandleMarker::new(token)));
+                //node and each of its ancestors now have one more marked node in their subtrees
+                let mut cur = Some(node);
+                while let Some(n) = cur {
+                    (*n.as_ptr()).marked_count += 1;
+                    cur = n.as_ref().parent_ptr;
+                }
+            }
+            Arc::downgrade(node.as_ref().handle_marker.as_ref().unwrap())
+        }
+    }
+
+    //mutates node's value via f, and recalculates the folds above it afterwards, even if f panics
+    pub(crate) unsafe fn handle_update_value<R>(&mut self, node: NonNull<WAVLNode<T,D>>, f: impl FnOnce(&mut T) -> R) -> R {
+        unsafe { update_node_value(node, self.settings, f) }
+    }
+
+    //whether the node of marker is in this chain
+    pub(crate) fn owns_handle_marker(&self, marker: &HandleMarker) -> bool {
+        self.handle_token.as_ref().is_some_and(|token| Arc::as_ptr(token) as usize == marker.owner())
+    }
+
+    fn marked_count(&self) -> usize {
+        self.root.as_ref().map_or(0, |root| root.marked_count)
+    }
+
+    //points the markers of this chain's nodes to token, only visiting the subtrees which have some
+    fn retag_handles(&self, token: &Arc<()>) {
+        let mut stack: Vec<&WAVLNode<T,D>> = self.root.as_deref().filter(|root| root.marked_count > 0).into_iter().collect();
+        while let Some(node) = stack.pop() {
+            if let Some(marker) = &node.handle_marker {
+                marker.set_owner(token);
+            }
+            stack.extend(node.left.as_deref().filter(|l| l.marked_count > 0));
+            stack.extend(node.right.as_deref().filter(|r| r.marked_count > 0));
+        }
+    }
+
+    //gives this chain one token for its own markers and those of other, whose nodes are being moved into it,
+    //by pointing the markers of whichever of the two has fewer to the token of the other
+    fn merge_handle_tokens(&mut self, other: &mut FoldChain<T,D,Settings>) {
+        let Some(other_token) = other.handle_token.take() else { return };
+        let other_count = other.marked_count();
+        if other_count == 0 {
+            return;
+        }
+        match &self.handle_token {
+            Some(token) if other_count <= self.marked_count() => other.retag_handles(token),
+            _ => {
+                self.retag_handles(&other_token);
+                self.handle_token = Some(other_token);
+            },
+        }
+    }
+
+    //gives taken, whose nodes were just split off from this chain, a token of its own,
+    //by pointing the markers of whichever of the two has fewer to a new token
+    fn split_handle_tokens(&mut self, taken: &mut FoldChain<T,D,Settings>) {
+        let taken_count = taken.marked_count();
+        if taken_count == 0 {
+            return;
+        }
+        let new_token = Arc::new(());
+        if taken_count <= self.marked_count() {
+            taken.retag_handles(&new_token);
+            taken.handle_token = Some(new_token);
+        } else {
+            self.retag_handles(&new_token);
+            taken.handle_token = self.handle_token.replace(new_token);
+        }
+    }
+}
+
+impl<T, D: Clone, Settings: FoldSettings<T,D>> FoldChain<T, (usize,D), SettingsWithSize<Settings>> {
+    pub(crate) fn cursor_node_at_index(&self, mut index: usize) -> Option<NonNull<WAVLNode<T,(usize,D)>>> {
+        let mut cur = self.root.as_deref()?;
+        loop {
+            let left_size = cur.left.as_ref().map_or(0, |l| l.delta_whole.0);
+            if index < left_size {
+                cur = cur.left.as_deref()?;
+            } else if index == left_size {
+                return Some(cur.into())
+            } else {
+                index -= left_size + 1;
+                cur = cur.right.as_deref()?;
+            }
+        }
+    }
+
+    //the prefix folds at each of indices, which must be sorted and at most the length, from one descent which splits indices between subtrees.
+    //this visits the union of the paths to indices, which is O(k log(n/k)) nodes for k indices
+    pub(crate) fn prefix_folds_at(&self, indices: &[usize]) -> Vec<(usize,D)> {
+        let settings = self.settings;
+        let mut ret = Vec::with_capacity(indices.len());
+        match &self.root {
+            Some(root) => root.prefix_folds_at(indices, 0, settings.empty(), settings, &mut ret),
+            None => ret.extend(indices.iter().map(|_| settings.empty())),
+        }
+        ret
+    }
+}
+
+impl<T, D: Clone> WAVLNode<T,(usize,D)> {
+    //offset is the index of the leftmost element of this subtree, acc is the fold of everything left of it, and indices are all within offset..=offset+size
+    fn prefix_folds_at<Settings: FoldSettings<T,D>>(&self, indices: &[usize], offset: usize, acc: (usize,D), settings: SettingsWithSize<Settings>, out: &mut Vec<(usize,D)>) {
+        let left_size = self.left.as_ref().map_or(0, |l| l.delta_whole.0);
+        let (in_left, in_right) = indices.split_at(indices.partition_point(|&i| i <= offset + left_size));
+        match &self.left {
+            Some(l) => l.prefix_folds_at(in_left, offset, acc.clone(), settings, out),
+            None => out.extend(in_left.iter().map(|_| acc.clone())),
+        }
+        if in_right.is_empty() {
+            return;
+        }
+        let mut acc = acc;
+        if let Some(l) = &self.left {
+            acc = settings.op(acc, l.delta_whole.clone());
+        }
+        acc = settings.op(acc, settings.delta_of(&self.value));
+        match &self.right {
+            Some(r) => r.prefix_folds_at(in_right, offset + left_size + 1, acc, settings, out),
+            None => out.extend(in_right.iter().map(|_| acc.clone())),
+        }
+    }
+}
+
+//folds of slices computed from prefix folds, for settings which can subtract; see crate::fold_list
+impl<'a, IsReversed: Bool, IsFlushLeft: Bool, IsFlushRight: Bool, Settings: InvertibleFoldSettings<T,D> + 'a, Simplification: FoldSimplification<T,D> + 'a, T: 'a, D: Clone + 'a> 
+ImmFoldChainSliceStruct<'a, IsReversed, IsFlushLeft, IsFlushRight, Settings, Simplification, T, D> {
+    //the fold of this slice without its simplification, as the fold up to its right end minus the fold up to its left end
+    pub(crate) fn unsimplified_fold_by_prefixes(&self) -> D {
+        let settings = self.settings;
+        let Some(ImmSliceEndpoints { left, right, root }) = self.endpoints else { return settings.empty() };
+        unsafe {
+            let through_right = if IsFlushRight::b {
+                root.as_ref().delta_whole.clone()
+            } else {
+                settings.op(WAVLNode::fold_before(right, settings), settings.delta_of(&right.as_ref().value))
+            };
+            if IsFlushLeft::b {
+                through_right
+            } else {
+                settings.subtract(through_right, WAVLNode::fold_before(left, settings))
+            }
+        }
+    }
+}
+
+//node-level operations used by the parallel iterators and parallel mutation; see crate::rayon
+#[cfg(feature = "rayon")]
+impl<T, D: Clone> WAVLNode<T,(usize,D)> {
+    //the node at index within the subtree rooted at root, which must be in bounds
+    pub(crate) unsafe fn rayon_node_at_index(root: NonNull<Self>, mut index: usize) -> NonNull<Self> {
+        let mut cur = unsafe { root.as_ref() };
+        loop {
+            let left_size = cur.left.as_ref().map_or(0, |l| l.delta_whole.0);
+            if index < left_size {
+                cur = cur.left.as_deref().unwrap();
+            } else if index == left_size {
+                return cur.into()
+            } else {
+                index -= left_size + 1;
+                cur = cur.right.as_deref().unwrap();
+            }
+        }
+    }
+
+    //the index of node within its whole tree
+    pub(crate) unsafe fn rayon_index_of(node: NonNull<Self>) -> usize {
+        let mut cur = unsafe { node.as_ref() };
+        let mut index = cur.left.as_ref().map_or(0, |l| l.delta_whole.0);
+        while let Some(parent) = cur.parent_ptr {
+            let parent = unsafe { parent.as_ref() };
+            if cur.is_right_child {
+                index += parent.left.as_ref().map_or(0, |l| l.delta_whole.0) + 1;
+            }
+            cur = parent;
+        }
+        index
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<'a, IsReversed: Bool, IsFlushLeft: Bool, IsFlushRight: Bool, Settings: FoldSettings<T,(usize,D)> + 'a, Simplification: FoldSimplification<T,(usize,D)> + 'a, T: 'a, D: Clone + 'a> 
+ImmFoldChainSliceStruct<'a, IsReversed, IsFlushLeft, IsFlushRight, Settings, Simplification, T, (usize,D)> {
+    //the root of the tree, and the range of indices within it which this slice covers, from left to right regardless of reversal
+    pub(crate) fn rayon_range(&self) -> Option<(NonNull<WAVLNode<T,(usize,D)>>,core::ops::Range<usize>)> {
+        let ImmSliceEndpoints { left, right, root } = self.endpoints.as_ref()?;
+        unsafe { Some((*root, WAVLNode::rayon_index_of(*left)..WAVLNode::rayon_index_of(*right) + 1)) }
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<'a, REVERSED: Bool, T: 'a, D: Clone + 'a> Iter<'a, REVERSED, T, (usize,D)> {
+    //an iterator over the given range of the tree rooted at root, which must be in bounds
+    pub(crate) unsafe fn rayon_from_range(root: Option<NonNull<WAVLNode<T,(usize,D)>>>, range: core::ops::Range<usize>) -> Self {
+        let next_and_next_back = match root {
+            Some(root) if !range.is_empty() => unsafe { Some((WAVLNode::rayon_node_at_index(root, range.start), WAVLNode::rayon_node_at_index(root, range.end - 1))) },
+            _ => None,
+        };
+        Self { next_and_next_back, _m: PhantomData }
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T: Send, D: Clone + Send, Settings: FoldSettings<T,D> + Send + Sync> FoldChain<T, D, Settings> {
+    //calls f on every element in parallel, splitting the tree at each node, and recalculates the folds on the way back up
+    pub(crate) fn rayon_for_each_mut(&mut self, f: &(impl Fn(&mut T) + Sync)) {
+        //subtrees of at most this rank are handled on a single thread
+        const SEQUENTIAL_RANK: u8 = 12;
+        //nodes aren't Send because of their parent pointers, but each thread only touches its own subtree
+        struct SendMut<'b,X>(&'b mut X);
+        unsafe impl<'b,T: Send,D: Send> Send for SendMut<'b,WAVLNode<T,D>> {}
+        impl<'b,X> SendMut<'b,X> {
+            fn get(self) -> &'b mut X {
+                self.0
+            }
+        }
+        //recalculates a node's fold when dropped, so that the folds stay right even if f panics on it or on one of its descendants
+        struct RecalcDeltaGuard<T,D: Clone,Settings: FoldSettings<T,D>> {
+            node: *mut WAVLNode<T,D>,
+            settings: Settings,
+        }
+        impl<T,D: Clone,Settings: FoldSettings<T,D>> Drop for RecalcDeltaGuard<T,D,Settings> {
+            fn drop(&mut self) {
+                unsafe { (*self.node).recalc_delta(self.settings) }
+            }
+        }
+        fn for_each_mut_node<T: Send, D: Clone + Send>(node: &mut WAVLNode<T,D>, f: &(impl Fn(&mut T) + Sync), settings: impl FoldSettings<T,D> + Send + Sync) {
+            let node: *mut WAVLNode<T,D> = node;
+            let _guard = RecalcDeltaGuard { node, settings };
+            let node = unsafe { &mut *node };
+            if node.rank <= SEQUENTIAL_RANK {
+                if let Some(l) = node.left.as_deref_mut() { for_each_mut_node(l, f, settings); }
+                if let Some(r) = node.right.as_deref_mut() { for_each_mut_node(r, f, settings); }
+            } else {
+                let left = node.left.as_deref_mut().map(SendMut);
+                let right = node.right.as_deref_mut().map(SendMut);
+                ::rayon::join(
+                    || if let Some(l) = left { for_each_mut_node(l.get(), f, settings) },
+                    || if let Some(r) = right { for_each_mut_node(r.get(), f, settings) },
+                );
+            }
+            f(&mut node.value);
+        }
+        let settings = self.settings;
+        if let Some(root) = self.root.as_deref_mut() {
+            for_each_mut_node(root, f, settings);
+        }
+    }
+}
+
 impl<'a,T, D: Clone, Settings: FoldSettings<T,D>> FoldChainSlice<'a,T,D> for &'a FoldChain<T, D, Settings> {
     type OriginalD = D;
     type IsReversed = False;
@@ -1848,11 +2362,12 @@ unsafe fn endpoints_push_left_template<Reversed: Bool, Settings: FoldSettings<T,
     right_opt_mut: &mut Option<NonNull<WAVLNode<T,D>>>,
     root: NonNull<Option<Box<WAVLNode<T,D>>>>,
     settings: Settings,
-    value: T
+    value: T,
+    pool: &mut NodePool<T,D>
 ) {
     unsafe {
         if left_opt_mut.is_some() {
-            let new = WAVLNode::new_leaf(settings,value);
+            let new = WAVLNode::new_leaf(settings,value,pool);
             let new_ptr = NonNull::from(new.as_ref().unwrap().as_ref());
             let left_mut = left_opt_mut.as_mut().unwrap();
             WAVLNode::mutate_box_of_and_update_parents(*left_mut, root, settings, |left| {
@@ -1864,7 +2379,7 @@ unsafe fn endpoints_push_left_template<Reversed: Bool, Settings: FoldSettings<T,
             }
         } else {
             if right_opt_mut.is_some() {
-                endpoints_push_left_template::<Reversed::Not,_,_,_>(right_opt_mut, left_opt_mut, root,settings, value)
+                endpoints_push_left_template::<Reversed::Not,_,_,_>(right_opt_mut, left_opt_mut, root,settings, value, pool)
             } else {
                 unreachable!()
             }
@@ -2280,6 +2795,48 @@ impl<T, D: Clone> ImmSliceEndpoints<T, D> {
         }
     }
 
+    //finds the first node from the left whose inclusion makes the fold satisfy predicate, along with the fold of everything before it
+    fn find_left_template<Reversed: Bool, IsFlushLeft: Bool, IsFlushRight: Bool, Settings: FoldSettings<T,D>, Simplification: FoldSimplification<T,D>>(
+        &self,
+        settings: Settings,
+        simp: Simplification,
+        predicate: impl Fn(&Simplification::D2) -> bool
+    ) -> (Simplification::D2, Option<NonNull<WAVLNode<T,D>>>) {
+        unsafe {
+            let left_if = IsFlushLeft::init_if_else((), |()| (), |()| self.left);
+            let right_if = IsFlushRight::init_if_else((), |()| (), |()| self.right);
+            let pre_fold = simp.empty(settings);
+            if Reversed::b {
+                let root_if = <IsFlushRight::And<IsFlushLeft> as Bool>::init_if_else((), |()| self.root, |()| ());
+                node_of_first_where_fold_left_is_template::<Reversed,IsFlushRight,IsFlushLeft,_,_,_,_>(right_if, left_if, root_if, settings, simp, predicate, pre_fold)
+            } else {
+                let root_if = <IsFlushLeft::And<IsFlushRight> as Bool>::init_if_else((), |()| self.root, |()| ());
+                node_of_first_where_fold_left_is_template::<Reversed,IsFlushLeft,IsFlushRight,_,_,_,_>(left_if, right_if, root_if, settings, simp, predicate, pre_fold)
+            }
+        }
+    }
+
+    //the mirror image of find_left_template, where the returned fold is of everything after the node
+    fn find_right_template<Reversed: Bool, IsFlushLeft: Bool, IsFlushRight: Bool, Settings: FoldSettings<T,D>, Simplification: FoldSimplification<T,D>>(
+        &self,
+        settings: Settings,
+        simp: Simplification,
+        predicate: impl Fn(&Simplification::D2) -> bool
+    ) -> (Simplification::D2, Option<NonNull<WAVLNode<T,D>>>) {
+        unsafe {
+            let left_if = IsFlushLeft::init_if_else((), |()| (), |()| self.left);
+            let right_if = IsFlushRight::init_if_else((), |()| (), |()| self.right);
+            let pre_fold = simp.empty(settings);
+            if Reversed::b {
+                let root_if = <IsFlushLeft::And<IsFlushRight> as Bool>::init_if_else((), |()| self.root, |()| ());
+                node_of_first_where_fold_left_is_template::<Reversed::Not,IsFlushLeft,IsFlushRight,_,_,_,_>(left_if, right_if, root_if, settings, simp, predicate, pre_fold)
+            } else {
+                let root_if = <IsFlushRight::And<IsFlushLeft> as Bool>::init_if_else((), |()| self.root, |()| ());
+                node_of_first_where_fold_left_is_template::<Reversed::Not,IsFlushRight,IsFlushLeft,_,_,_,_>(right_if, left_if, root_if, settings, simp, predicate, pre_fold)
+            }
+        }
+    }
+
     fn debug_check_structural_integrity_orig(&self) -> bool {
         debug_assert!(WAVLNode::debug_assert_a_has_parent_in_common_with_b_and_is_not_after(self.left, self.right));
         true
@@ -2326,6 +2883,16 @@ impl<'a, IsReversed: Bool, IsFlushLeft: Bool, IsFlushRight: Bool, Settings: Fold
         }
     }
 
+    pub(crate) fn find_left_consume(self, predicate: impl Fn(&Simplification::D2)->bool) -> Option<(&'a T, Simplification::D2)> {
+        let (d,node) = self.endpoints?.find_left_template::<IsReversed,IsFlushLeft,IsFlushRight,_,_>(self.settings, self.simplification, predicate);
+        unsafe {Some((&node?.as_ref().value, d))}
+    }
+
+    pub(crate) fn find_right_consume(self, predicate: impl Fn(&Simplification::D2)->bool) -> Option<(&'a T, Simplification::D2)> {
+        let (d,node) = self.endpoints?.find_right_template::<IsReversed,IsFlushLeft,IsFlushRight,_,_>(self.settings, self.simplification, predicate);
+        unsafe {Some((&node?.as_ref().value, d))}
+    }
+
     pub(crate) fn iter_consume(self) -> Iter<'a, IsReversed, T, D> {
         match self.endpoints {
             Some(ImmSliceEndpoints { left, right, root: _ }) => 
@@ -2952,6 +3519,7 @@ impl<'a, IsFlushLeft: Bool, IsFlushRight: Bool, Settings: FoldSettings<T,D>, T,
             if root.is_none() {return None}
             let settings = self.base.as_ref().settings;
             let root_ptr = NonNull::from(root);
+            let pool = &raw mut (*self.base.as_ptr()).pool;
             let (left_mut,right_mut) = self.left_right_opt_mut_opt()?;
             let (l,r) = cswap::<Reversed,_>(left_mut, right_mut);
             let to_pop = if *l == *r {
@@ -2964,7 +3532,7 @@ impl<'a, IsFlushLeft: Bool, IsFlushRight: Bool, Settings: FoldSettings<T,D>, T,
                 former_left
             };
             return WAVLNode::mutate_box_of_and_update_parents(to_pop, root_ptr, settings, |b| {
-                Some(WAVLNode::pop_top_in_place_boxed(b, settings).unwrap().value)
+                Some((*pool).free(WAVLNode::pop_top_in_place_boxed(b, settings).unwrap()).value)
             })
         }
     }
@@ -2973,11 +3541,12 @@ impl<'a, IsFlushLeft: Bool, IsFlushRight: Bool, Settings: FoldSettings<T,D>, T,
         unsafe {
             let settings = self.base.as_ref().settings;
             let root = NonNull::from(&self.base.as_ref().root);
+            let pool = &raw mut (*self.base.as_ptr()).pool;
             let (left_mut,right_mut) = self.left_right_opt_mut();
             if left_mut.is_none() && right_mut.is_none() {
                 debug_assert!(self.base.as_ref().root.is_none());
                 let base = self.base.as_mut();
-                let leaf = WAVLNode::new_leaf(settings, value);
+                let leaf = WAVLNode::new_leaf(settings, value, &mut base.pool);
                 let leaf_addr = leaf.as_ref().map(|n| NonNull::from(n.as_ref()));
                 let None = core::mem::replace(&mut base.root,leaf) else {unreachable!()};
                 base.leftmost_node_ptr = leaf_addr;
@@ -2995,7 +3564,7 @@ impl<'a, IsFlushLeft: Bool, IsFlushRight: Bool, Settings: FoldSettings<T,D>, T,
                 return
             }
             let (l,r) = cswap::<Reversed,_>(left_mut, right_mut);
-            endpoints_push_left_template::<Reversed,_,_,_>(l, r, root, settings, value);
+            endpoints_push_left_template::<Reversed,_,_,_>(l, r, root, settings, value, &mut *pool);
         }
     }
 
@@ -3015,20 +3584,28 @@ impl<'a, IsFlushLeft: Bool, IsFlushRight: Bool, Settings: FoldSettings<T,D>, T,
             let Some((left_mut,right_mut)) = self.left_right_opt_mut_opt() else {return f(None)};
             let left = if Reversed::b {right_mut} else {left_mut}
                 .as_mut().unwrap().as_mut();
-            let ret = f(Some(&mut left.value));
-            bubble_up_fold_from_node(left.into(), self.base.as_ref().settings);
-            ret
+            update_node_value(left.into(), self.base.as_ref().settings, |t| f(Some(t)))
         }
     }
 
     fn take_all_template(&mut self) -> FoldChain<T,D,Settings> {
         unsafe {
             let settings = self.base.as_ref().settings;
+            let pool = self.base.as_ref().pool.empty_like();
             if IsFlushLeft::b && IsFlushRight::b {
-                return core::mem::replace(&mut self.base.as_mut(),FoldChain::from_settings(settings))
+                let base = self.base.as_mut();
+                return FoldChain {
+                    root: base.root.take(),
+                    leftmost_node_ptr: base.leftmost_node_ptr.take(),
+                    rightmost_node_ptr: base.rightmost_node_ptr.take(),
+                    settings,
+                    pool,
+                    #[cfg(feature = "handles")]
+                    handle_token: base.handle_token.take(),
+                }
             }
             let Some(ImmSliceEndpoints { left, right, root: _ }) = self.to_imm() else {
-                return FoldChain::from_settings(settings);
+                return FoldChain { root: None, leftmost_node_ptr: None, rightmost_node_ptr: None, settings, pool, #[cfg(feature = "handles")] handle_token: None };
             };
             self.endpoints_become_empty();
             let root_ptr = NonNull::from(&mut self.base.as_mut().root);
@@ -3045,24 +3622,36 @@ impl<'a, IsFlushLeft: Bool, IsFlushRight: Bool, Settings: FoldSettings<T,D>, T,
                 },
             };
             taken.parent_ptr = None;
-            FoldChain {
+            #[cfg_attr(not(feature = "handles"), allow(unused_mut))]
+            let mut ret = FoldChain {
                 leftmost_node_ptr: Some(taken.all_the_way_left_template::<False>().into()),
                 rightmost_node_ptr: Some(taken.all_the_way_left_template::<True>().into()),
                 root: Some(taken),
                 settings,
-            }
+                pool,
+                #[cfg(feature = "handles")]
+                handle_token: None,
+            };
+            #[cfg(feature = "handles")]
+            self.base.as_mut().split_handle_tokens(&mut ret);
+            ret
         }
     }
 
-    fn append_all_right_template<Reversed: Bool>(&mut self, other: FoldChain<T,D,Settings>) {
+    #[cfg_attr(not(feature = "handles"), allow(unused_mut))]
+    fn append_all_right_template<Reversed: Bool>(&mut self, mut other: FoldChain<T,D,Settings>) {
         unsafe {
+            #[cfg(feature = "handles")]
+            self.base.as_mut().merge_handle_tokens(&mut other);
             let root = NonNull::from(&self.base.as_ref().root);
             let settings = self.base.as_ref().settings;
             let (left_mut,right_mut) = self.left_right_opt_mut();
             if left_mut.is_none() && right_mut.is_none() {
                 let base_mut = self.base.as_mut();
                 debug_assert!(base_mut.root.is_none());
-                *base_mut = other;
+                base_mut.root = other.root;
+                base_mut.leftmost_node_ptr = other.leftmost_node_ptr;
+                base_mut.rightmost_node_ptr = other.rightmost_node_ptr;
                 self.left_right = IsFlushLeft::init_if_else((), 
                     |()| IsFlushRight::init_if_else((), 
                         |()| (), 
@@ -3145,6 +3734,14 @@ impl<'a, T, D: Clone, Settings: FoldSettings<T,D> + 'a> MutFoldChainSliceStruct<
     }
 }
 
+impl<'a, IsReversed: Bool, IsFlushLeft: Bool, IsFlushRight: Bool, T, D: Clone, Settings: FoldSettings<T,D> + 'a, Simplification: FoldSimplification<T,D> + 'a> 
+MutFoldChainSliceStruct<'a, IsReversed, IsFlushLeft, IsFlushRight, T, D, Settings, Simplification> {
+    //the node pool of the base chain, which nothing else in the base borrows
+    pub(crate) fn pool_mut(&mut self) -> &mut NodePool<T,D> {
+        unsafe { &mut (*self.endpoints.base.as_ptr()).pool }
+    }
+}
+
 impl<'a, IsReversed: Bool, IsFlushLeft: Bool, IsFlushRight: Bool, T, D: Clone, Settings: FoldSettings<T,D> + 'a, Simplification: FoldSimplification<T,D> + 'a> 
 FoldChainSlice<'a,T,Simplification::D2> for MutFoldChainSliceStruct<'a, IsReversed, IsFlushLeft, IsFlushRight, T, D, Settings, Simplification> {
     type OriginalD = D;
@@ -3279,7 +3876,10 @@ impl<T: Clone, D: Clone, Settings: FoldSettings<T,D>> Clone for FoldChain<T, D,
             leftmost_node_ptr: root2.as_ref().map(|r| r.all_the_way_left_template::<False>().into()), 
             rightmost_node_ptr: root2.as_ref().map(|r| r.all_the_way_left_template::<True>().into()), 
             root: root2, 
-            settings: self.settings.clone() 
+            settings: self.settings.clone(),
+            pool: self.pool.empty_like(),
+            #[cfg(feature = "handles")]
+            handle_token: None,
         }
     }
 }
@@ -3410,21 +4010,143 @@ IntoIterator for &'b MutFoldChainSliceStruct<'a, IsReversed, IsFlushLeft, IsFlus
 
 impl<T: core::fmt::Debug, D: Clone, Settings: FoldSettings<T,D>> 
 core::fmt::Debug for FoldChain<T, D, Settings> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_list().entries(self.iter()).finish()
     }
 }
 
 impl<'a, IsReversed: Bool, IsFlushLeft: Bool, IsFlushRight: Bool, Settings: FoldSettings<T,D> + 'a, Simplification: FoldSimplification<T,D> + 'a, T: core::fmt::Debug + 'a, D: Clone + 'a> 
 core::fmt::Debug for ImmFoldChainSliceStruct<'a, IsReversed, IsFlushLeft, IsFlushRight, Settings, Simplification, T, D> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_list().entries(self.iter()).finish()
     }
 }
 
 impl<'a, IsReversed: Bool, IsFlushLeft: Bool, IsFlushRight: Bool, T: core::fmt::Debug, D: Clone, Settings: FoldSettings<T,D> + 'a, Simplification: FoldSimplification<T,D> + 'a> 
 core::fmt::Debug for MutFoldChainSliceStruct<'a, IsReversed, IsFlushLeft, IsFlushRight, T, D, Settings, Simplification> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_list().entries(self.iter()).finish()    
     }
-}
\ No newline at end of file
+}
+
+//comparisons and hashing look at the elements in order, like for slices
+
+impl<T: PartialEq<U>, U, D: Clone, D2: Clone, Settings: FoldSettings<T,D>, Settings2: FoldSettings<U,D2>> 
+PartialEq<FoldChain<U, D2, Settings2>> for FoldChain<T, D, Settings> {
+    fn eq(&self, other: &FoldChain<U, D2, Settings2>) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T: PartialEq<U>, U, D: Clone, Settings: FoldSettings<T,D>> 
+PartialEq<[U]> for FoldChain<T, D, Settings> {
+    fn eq(&self, other: &[U]) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T: PartialEq<U>, U, D: Clone, Settings: FoldSettings<T,D>> 
+PartialEq<Vec<U>> for FoldChain<T, D, Settings> {
+    fn eq(&self, other: &Vec<U>) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq, D: Clone, Settings: FoldSettings<T,D>> Eq for FoldChain<T, D, Settings> {}
+
+impl<T: PartialOrd, D: Clone, D2: Clone, Settings: FoldSettings<T,D>, Settings2: FoldSettings<T,D2>> 
+PartialOrd<FoldChain<T, D2, Settings2>> for FoldChain<T, D, Settings> {
+    fn partial_cmp(&self, other: &FoldChain<T, D2, Settings2>) -> Option<core::cmp::Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord, D: Clone, Settings: FoldSettings<T,D>> Ord for FoldChain<T, D, Settings> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: core::hash::Hash, D: Clone, Settings: FoldSettings<T,D>> core::hash::Hash for FoldChain<T, D, Settings> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        //chains don't keep their length, so it's counted while hashing the elements and written after them
+        let mut len = 0;
+        self.iter().for_each(|t| { t.hash(state); len += 1; });
+        state.write_usize(len);
+    }
+}
+
+impl<'a, 'b, IsReversed: Bool, IsFlushLeft: Bool, IsFlushRight: Bool, Settings: FoldSettings<T,D> + 'a, Simplification: FoldSimplification<T,D> + 'a, IsReversed2: Bool, IsFlushLeft2: Bool, IsFlushRight2: Bool, Settings2: FoldSettings<U,D2> + 'b, Simplification2: FoldSimplification<U,D2> + 'b, T: PartialEq<U> + 'a, U: 'b, D: Clone + 'a, D2: Clone + 'b> 
+PartialEq<ImmFoldChainSliceStruct<'b, IsReversed2, IsFlushLeft2, IsFlushRight2, Settings2, Simplification2, U, D2>> for ImmFoldChainSliceStruct<'a, IsReversed, IsFlushLeft, IsFlushRight, Settings, Simplification, T, D> {
+    fn eq(&self, other: &ImmFoldChainSliceStruct<'b, IsReversed2, IsFlushLeft2, IsFlushRight2, Settings2, Simplification2, U, D2>) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<'a, IsReversed: Bool, IsFlushLeft: Bool, IsFlushRight: Bool, Settings: FoldSettings<T,D> + 'a, Simplification: FoldSimplification<T,D> + 'a, T: PartialEq<U> + 'a, U, D: Clone + 'a> 
+PartialEq<[U]> for ImmFoldChainSliceStruct<'a, IsReversed, IsFlushLeft, IsFlushRight, Settings, Simplification, T, D> {
+    fn eq(&self, other: &[U]) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<'a, IsReversed: Bool, IsFlushLeft: Bool, IsFlushRight: Bool, Settings: FoldSettings<T,D> + 'a, Simplification: FoldSimplification<T,D> + 'a, T: PartialEq<U> + 'a, U, D: Clone + 'a> 
+PartialEq<Vec<U>> for ImmFoldChainSliceStruct<'a, IsReversed, IsFlushLeft, IsFlushRight, Settings, Simplification, T, D> {
+    fn eq(&self, other: &Vec<U>) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<'a, IsReversed: Bool, IsFlushLeft: Bool, IsFlushRight: Bool, Settings: FoldSettings<T,D> + 'a, Simplification: FoldSimplification<T,D> + 'a, T: Eq + 'a, D: Clone + 'a> 
+Eq for ImmFoldChainSliceStruct<'a, IsReversed, IsFlushLeft, IsFlushRight, Settings, Simplification, T, D> {}
+
+impl<'a, 'b, IsReversed: Bool, IsFlushLeft: Bool, IsFlushRight: Bool, Settings: FoldSettings<T,D> + 'a, Simplification: FoldSimplification<T,D> + 'a, IsReversed2: Bool, IsFlushLeft2: Bool, IsFlushRight2: Bool, Settings2: FoldSettings<T,D2> + 'b, Simplification2: FoldSimplification<T,D2> + 'b, T: PartialOrd + 'a + 'b, D: Clone + 'a, D2: Clone + 'b> 
+PartialOrd<ImmFoldChainSliceStruct<'b, IsReversed2, IsFlushLeft2, IsFlushRight2, Settings2, Simplification2, T, D2>> for ImmFoldChainSliceStruct<'a, IsReversed, IsFlushLeft, IsFlushRight, Settings, Simplification, T, D> {
+    fn partial_cmp(&self, other: &ImmFoldChainSliceStruct<'b, IsReversed2, IsFlushLeft2, IsFlushRight2, Settings2, Simplification2, T, D2>) -> Option<core::cmp::Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<'a, IsReversed: Bool, IsFlushLeft: Bool, IsFlushRight: Bool, Settings: FoldSettings<T,D> + 'a, Simplification: FoldSimplification<T,D> + 'a, T: Ord + 'a, D: Clone + 'a> 
+Ord for ImmFoldChainSliceStruct<'a, IsReversed, IsFlushLeft, IsFlushRight, Settings, Simplification, T, D> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<'a, IsReversed: Bool, IsFlushLeft: Bool, IsFlushRight: Bool, Settings: FoldSettings<T,D> + 'a, Simplification: FoldSimplification<T,D> + 'a, T: core::hash::Hash + 'a, D: Clone + 'a> 
+core::hash::Hash for ImmFoldChainSliceStruct<'a, IsReversed, IsFlushLeft, IsFlushRight, Settings, Simplification, T, D> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        //chains don't keep their length, so it's counted while hashing the elements and written after them
+        let mut len = 0;
+        self.iter().for_each(|t| { t.hash(state); len += 1; });
+        state.write_usize(len);
+    }
+}
+
+impl<T, D: Clone, Settings: FoldSettings<T,D> + Default> Default for FoldChain<T, D, Settings> {
+    fn default() -> Self {
+        Self::from_settings(Settings::default())
+    }
+}
+
+impl<T, D: Clone, Settings: FoldSettings<T,D> + Default> FromIterator<T> for FoldChain<T, D, Settings> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut ret = Self::default();
+        ret.append_right_from_iter(iter.into_iter());
+        ret
+    }
+}
+
+impl<T, D: Clone, Settings: FoldSettings<T,D>> Extend<T> for FoldChain<T, D, Settings> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.append_right_from_iter(iter.into_iter());
+    }
+}
+
+impl<'a, IsReversed: Bool, IsFlushLeft: Bool, IsFlushRight: Bool, T, D: Clone, Settings: FoldSettings<T,D> + 'a, Simplification: FoldSimplification<T,D> + 'a> 
+Extend<T> for MutFoldChainSliceStruct<'a, IsReversed, IsFlushLeft, IsFlushRight, T, D, Settings, Simplification> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        MutFoldChainSlice::append_right_from_iter(self, iter.into_iter());
+    }
+}
@@ -1,6 +1,9 @@
-use core::marker::PhantomData;
+use core::{marker::PhantomData, ops::{Bound, RangeBounds}};
+use alloc::vec::Vec;
 
-use crate::{fold_chain::{self, Drain, FoldChain, FoldChainSlice, ImmFoldChainSliceStruct, Iter, MutFoldChainSlice, MutFoldChainSliceStruct}, fold_settings::{FoldSettings, FoldSettingsStruct, SettingsWithSize}, fold_simplification::{FoldSimplification, IgnoringSize, OpFromSettings, SimplificationWithShortcut, SimplificationWithoutShortcut, SizeIgnoreFn}, misc::{Bool, EmptyFn, False, Fun, NoneFun, OptOpFun, SingleEndedRange, SomeFun, True, TupleFun, private::Sealed}};
+use crate::{cursor::{Cursor, CursorMut, ElementMut}, node_pool::NodePool, fold_chain::{self, Drain, FoldChain, FoldChainSlice, ImmFoldChainSliceStruct, Iter, MutFoldChainSlice, MutFoldChainSliceStruct}, fold_settings::{FoldSettings, FoldSettingsStruct, InvertibleFoldSettings, SettingsWithSize}, fold_simplification::{FoldSimplification, IgnoringSize, OpFromSettings, SimplificationWithShortcut, SimplificationWithoutShortcut, SizeIgnoreFn}, misc::{Bool, EmptyFn, False, Fun, IndexOutOfBounds, NoneFun, OptOpFun, SingleEndedRange, SomeFun, True, TupleFun, private::Sealed}};
+#[cfg(feature = "handles")]
+use crate::handle::Handle;
 
 /// A base [FoldList](crate).
 #[derive(Clone)]
@@ -62,6 +65,209 @@ impl<T, D: Clone, Settings: FoldSettings<T,D>> FoldList<T, D, Settings> {
         }
     }
 
+    /// Create a new empty `FoldList`, with the specified [Settings](crate#fold-settings), which takes the allocations for new elements from `pool`,
+    /// and keeps the allocations of removed elements in it; see [`NodePool`].
+    pub fn from_settings_and_pool(settings: Settings, pool: NodePool<T,(usize,D)>) -> Self {
+        Self {
+            underlying: FoldChain::from_settings_and_pool(SettingsWithSize(settings), pool),
+        }
+    }
+
+    /// Drop every element, and return this list's [`NodePool`] with their allocations added to it, so that another list can reuse them.
+    pub fn into_node_pool(self) -> NodePool<T,(usize,D)> {
+        self.underlying.into_node_pool()
+    }
+
+    /// Get this list's [`NodePool`], which holds spare allocations for new elements.
+    pub fn node_pool(&self) -> &NodePool<T,(usize,D)> {
+        self.underlying.node_pool()
+    }
+
+    /// Get this list's [`NodePool`] mutably, e.g. to [make spare allocations](NodePool::ensure_capacity) for new elements.
+    pub fn node_pool_mut(&mut self) -> &mut NodePool<T,(usize,D)> {
+        self.underlying.node_pool_mut()
+    }
+
+    /// Get the number of elements this list can hold without allocating, like [`Vec::capacity`].
+    pub fn capacity(&self) -> usize {
+        self.len() + self.underlying.node_pool().capacity()
+    }
+
+    /// Free the spare allocations in this list's [`NodePool`].
+    pub fn shrink_to_fit(&mut self) {
+        self.underlying.shrink_to_fit();
+    }
+
+    /// Split this list in two at index `index`, like [`Vec::split_off`].
+    /// 
+    /// The elements at indices `index` and above are removed and returned as a new `FoldList`; the elements before `index` stay in `self`. This is *O*(log(n)).
+    /// 
+    /// Panics if `index` is outside ```0..=self.len()```.
+    pub fn split_off(&mut self, index: usize) -> FoldList<T, D, Settings> {
+        if index > self.len() {
+            panic!("Index out of bounds: the index is {} but the length is {}",index,self.len());
+        }
+        self.mut_view_drop_left(index).take_all()
+    }
+
+    /// Split this list in two at the first element which causes the fold from the left to satisfy `predicate`.
+    /// 
+    /// That element and everything to its right are removed and returned as a new `FoldList`; the elements before it stay in `self`.
+    /// If no element causes the fold to satisfy `predicate`, the returned list is empty.
+    /// 
+    /// `predicate` should be monotonic, as in [`mut_view_drop_left_until`](MutFoldListSlice::mut_view_drop_left_until). This is *O*(log(n)).
+    pub fn split_off_where(&mut self, predicate: impl Fn(&D)->bool) -> FoldList<T, D, Settings> {
+        self.mut_view_drop_left_until(predicate).take_all()
+    }
+
+    /// Get a [`Cursor`] pointing at the leftmost element, or at the ghost position if this list is empty.
+    pub fn cursor_front(&self) -> Cursor<'_, T, D, Settings> {
+        Cursor::new_at(self, 0)
+    }
+
+    /// Get a [`Cursor`] pointing at the rightmost element, or at the ghost position if this list is empty.
+    pub fn cursor_back(&self) -> Cursor<'_, T, D, Settings> {
+        Cursor::new_at(self, self.len().saturating_sub(1))
+    }
+
+    /// Get a [`Cursor`] pointing at the element at index `index`, or at the ghost position if `index` is `self.len()`. This is *O*(log(n)).
+    /// 
+    /// Panics if `index` is outside ```0..=self.len()```.
+    pub fn cursor_at(&self, index: usize) -> Cursor<'_, T, D, Settings> {
+        Cursor::new_at(self, index)
+    }
+
+    /// Get a [`CursorMut`] pointing at the leftmost element, or at the ghost position if this list is empty.
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T, D, Settings> {
+        CursorMut::new_at(self, 0)
+    }
+
+    /// Get a [`CursorMut`] pointing at the rightmost element, or at the ghost position if this list is empty.
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T, D, Settings> {
+        let index = self.len().saturating_sub(1);
+        CursorMut::new_at(self, index)
+    }
+
+    /// Get a [`CursorMut`] pointing at the element at index `index`, or at the ghost position if `index` is `self.len()`. This is *O*(log(n)).
+    /// 
+    /// Panics if `index` is outside ```0..=self.len()```.
+    pub fn cursor_at_mut(&mut self, index: usize) -> CursorMut<'_, T, D, Settings> {
+        CursorMut::new_at(self, index)
+    }
+
+    /// Get a mutable reference to the element at index `index`, wrapped in an [`ElementMut`] which updates this list's folds when dropped.
+    /// 
+    /// Panics if `index` is out of bounds.
+    /// 
+    /// For the non-panicking version, see [`try_get_mut`](FoldList::try_get_mut).
+    pub fn get_mut(&mut self, index: usize) -> ElementMut<'_, T, D, Settings> {
+        self.try_get_mut(index).unwrap_or_else(|e| panic!("{}",e))
+    }
+
+    /// Get a mutable reference to the element at index `index`, wrapped in an [`ElementMut`] which updates this list's folds when dropped.
+    /// 
+    /// If `index` is out of bounds, returns an [`IndexOutOfBounds`] error.
+    pub fn try_get_mut(&mut self, index: usize) -> Result<ElementMut<'_, T, D, Settings>, IndexOutOfBounds> {
+        let len = self.len();
+        if index >= len {
+            return Err(IndexOutOfBounds { index, len });
+        }
+        Ok(ElementMut::new_at(self, index))
+    }
+}
+
+//handles are opt-in; see crate::handle
+#[cfg(feature = "handles")]
+impl<T, D: Clone, Settings: FoldSettings<T,D>> FoldList<T, D, Settings> {
+    /// Append `value` to the left of this list, and return a [`Handle`] to it.
+    pub fn append_left_with_handle(&mut self, value: T) -> Handle<T, D> {
+        let leftmost = self.underlying.cursor_leftmost_node();
+        unsafe {
+            let node = self.underlying.cursor_insert_before(leftmost, value);
+            Handle::new::<Settings>(&mut self.underlying, node)
+        }
+    }
+
+    /// Append `value` to the right of this list, and return a [`Handle`] to it.
+    pub fn append_right_with_handle(&mut self, value: T) -> Handle<T, D> {
+        unsafe {
+            let node = self.underlying.cursor_insert_before(None, value);
+            Handle::new::<Settings>(&mut self.underlying, node)
+        }
+    }
+
+    /// Insert `value` at index `index`, like [`insert_at`](MutFoldListSlice::insert_at), and return a [`Handle`] to it. This is *O*(log(n)).
+    /// 
+    /// Panics if `index` is outside ```0..=self.len()```.
+    pub fn insert_at_with_handle(&mut self, index: usize, value: T) -> Handle<T, D> {
+        let len = self.len();
+        if index > len {
+            panic!("Index out of bounds: the index is {} but the length is {}",index,len);
+        }
+        let node = self.underlying.cursor_node_at_index(index);
+        unsafe {
+            let node = self.underlying.cursor_insert_before(node, value);
+            Handle::new::<Settings>(&mut self.underlying, node)
+        }
+    }
+
+    /// Get a [`Handle`] to the element at index `index`. This is *O*(log(n)).
+    /// 
+    /// Panics if `index` is out of bounds.
+    pub fn handle_at(&mut self, index: usize) -> Handle<T, D> {
+        let len = self.len();
+        let Some(node) = self.underlying.cursor_node_at_index(index).filter(|_| index < len) else {
+            panic!("{}",IndexOutOfBounds { index, len })
+        };
+        unsafe { Handle::new::<Settings>(&mut self.underlying, node) }
+    }
+
+    /// Get the current index of the element of `handle`, or [`None`] if it isn't in this list. This is *O*(log(n)).
+    pub fn index_of(&self, handle: &Handle<T, D>) -> Option<usize> {
+        let node = handle.node_in(self)?;
+        Some(unsafe { self.underlying.cursor_fold_before(Some(node)) }.0)
+    }
+
+    /// Get the fold of every element left of the element of `handle`, or [`None`] if it isn't in this list. This is *O*(log(n)).
+    pub fn prefix_fold_of(&self, handle: &Handle<T, D>) -> Option<D> {
+        let node = handle.node_in(self)?;
+        Some(unsafe { self.underlying.cursor_fold_before(Some(node)) }.1)
+    }
+
+    /// Get the element of `handle`, or [`None`] if it isn't in this list.
+    pub fn value_of(&self, handle: &Handle<T, D>) -> Option<&T> {
+        let node = handle.node_in(self)?;
+        Some(unsafe { FoldChain::<T,(usize,D),SettingsWithSize<Settings>>::cursor_value(node) })
+    }
+
+    /// Mutate the element of `handle` via a closure, and return the result of the closure, or return [`None`] if it isn't in this list. This is *O*(log(n)).
+    pub fn update_by_handle<R>(&mut self, handle: &Handle<T, D>, f: impl FnOnce(&mut T)->R) -> Option<R> {
+        let node = handle.node_in(self)?;
+        Some(unsafe { self.underlying.handle_update_value(node, f) })
+    }
+
+    /// Remove and return the element of `handle`, or return [`None`] if it isn't in this list. This is *O*(log(n)).
+    /// 
+    /// Afterwards, `handle` and its clones are no longer valid.
+    pub fn remove_by_handle(&mut self, handle: &Handle<T, D>) -> Option<T> {
+        let node = handle.node_in(self)?;
+        Some(unsafe { self.underlying.cursor_remove(node) })
+    }
+}
+
+impl<T, D: Clone, Settings: FoldSettings<T,D>> FoldList<T, D, Settings> {
+    /// Get the fold of the first `index` elements of this list. This is *O*(log(n)).
+    /// 
+    /// Panics if `index` is greater than ```self.len()```.
+    pub fn prefix_fold(&self, index: usize) -> D {
+        let len = self.len();
+        if index > len {
+            panic!("Index out of bounds: the index is {} but the length is {}",index,len);
+        }
+        let node = self.underlying.cursor_node_at_index(index);
+        unsafe { self.underlying.cursor_fold_before(node) }.1
+    }
+
     //below are redefinitions of the functions for FoldListSlice and MutFoldListSlice, to enable the use of e.g. list.f() instead of needing to write (&mut list).f()
     
     /// An alias of [`get_current_simplification`](FoldListSlice::get_current_simplification).
@@ -156,6 +362,14 @@ impl<T, D: Clone, Settings: FoldSettings<T,D>> FoldList<T, D, Settings> {
     pub fn view_drop_left(&self, n: usize) -> FoldListSliceFrom<'_, T, D, Settings, (), ImmFoldChainSliceStruct<'_, False, False, True, SettingsWithSize<Settings>, (), T, (usize, D)>> {
         FoldListSlice::view_drop_left(self, n)
     }
+    /// An alias of [`view_range`](FoldListSlice::view_range).
+    pub fn view_range(&self, range: impl RangeBounds<usize>) -> FoldListSliceFrom<'_, T, D, Settings, (), ImmFoldChainSliceStruct<'_, False, False, False, SettingsWithSize<Settings>, (), T, (usize, D)>> {
+        FoldListSlice::view_range(self, range)
+    }
+    /// An alias of [`try_view_range`](FoldListSlice::try_view_range).
+    pub fn try_view_range(&self, range: impl RangeBounds<usize>) -> Option<FoldListSliceFrom<'_, T, D, Settings, (), ImmFoldChainSliceStruct<'_, False, False, False, SettingsWithSize<Settings>, (), T, (usize, D)>>> {
+        FoldListSlice::try_view_range(self, range)
+    }
     /// An alias of [`view_reversed`](FoldListSlice::view_reversed).
     pub fn view_reversed(&self) -> FoldListSliceFrom<'_, T, D, Settings, (), ImmFoldChainSliceStruct<'_, True, True, True, SettingsWithSize<Settings>, (), T, (usize, D)>> {
         FoldListSlice::view_reversed(self)
@@ -200,6 +414,19 @@ impl<T, D: Clone, Settings: FoldSettings<T,D>> FoldList<T, D, Settings> {
     pub fn get<'b>(&'b self,index: usize) -> &'b T {
         foldlist_index_impl(self.borrow(), index)
     }
+    /// An alias of [`find_left`](FoldListSlice::find_left).
+    pub fn find_left(&self, predicate: impl Fn(&D)->bool) -> Option<(usize, &T, D)> {
+        foldlist_find_left_impl(self.borrow(), predicate)
+    }
+    /// An alias of [`find_right`](FoldListSlice::find_right).
+    pub fn find_right(&self, predicate: impl Fn(&D)->bool) -> Option<(usize, &T, D)> {
+        foldlist_find_right_impl(self.borrow(), predicate)
+    }
+    /// An alias of [`try_get`](FoldListSlice::try_get).
+    pub fn try_get(&self,index: usize) -> Result<&T, IndexOutOfBounds> {
+        self.view_drop_left(index).underlying.left_consume()
+            .ok_or_else(|| IndexOutOfBounds { index, len: self.len() })
+    }
     /// An alias of [`foreach`](FoldListSlice::foreach).
     pub fn foreach(&self, f: impl FnMut(&T)) {
         self.underlying.foreach(f);
@@ -293,6 +520,14 @@ impl<T, D: Clone, Settings: FoldSettings<T,D>> FoldList<T, D, Settings> {
     pub fn mut_view_take_left(&mut self, n: usize) -> FoldListSliceFrom<'_, T, D, Settings, (), MutFoldChainSliceStruct<'_, False, True, False, T, (usize, D), SettingsWithSize<Settings>, ()>> {
         MutFoldListSlice::mut_view_take_left(self, n)
     }
+    /// An alias of [`mut_view_range`](MutFoldListSlice::mut_view_range).
+    pub fn mut_view_range(&mut self, range: impl RangeBounds<usize>) -> FoldListSliceFrom<'_, T, D, Settings, (), MutFoldChainSliceStruct<'_, False, False, False, T, (usize, D), SettingsWithSize<Settings>, ()>> {
+        MutFoldListSlice::mut_view_range(self, range)
+    }
+    /// An alias of [`try_mut_view_range`](MutFoldListSlice::try_mut_view_range).
+    pub fn try_mut_view_range(&mut self, range: impl RangeBounds<usize>) -> Option<FoldListSliceFrom<'_, T, D, Settings, (), MutFoldChainSliceStruct<'_, False, False, False, T, (usize, D), SettingsWithSize<Settings>, ()>>> {
+        MutFoldListSlice::try_mut_view_range(self, range)
+    }
     /// An alias of [`mut_view_reversed`](MutFoldListSlice::mut_view_reversed).
     pub fn mut_view_reversed(&mut self) -> FoldListSliceFrom<'_, T, D, Settings, (), MutFoldChainSliceStruct<'_, True, True, True, T, (usize, D), SettingsWithSize<Settings>, ()>> {
         MutFoldListSlice::mut_view_reversed(self)
@@ -369,6 +604,22 @@ impl<T, D: Clone, Settings: FoldSettings<T,D>> FoldList<T, D, Settings> {
     pub fn remove_at(&mut self, index: usize) -> T {
         MutFoldListSlice::remove_at(&mut &mut *self, index)
     }
+    /// An alias of [`try_update_at`](MutFoldListSlice::try_update_at).
+    pub fn try_update_at<R>(&mut self, index: usize, f: impl FnOnce(&mut T)->R) -> Result<R, IndexOutOfBounds> {
+        MutFoldListSlice::try_update_at(&mut &mut *self, index, f)
+    }
+    /// An alias of [`try_set_at`](MutFoldListSlice::try_set_at).
+    pub fn try_set_at(&mut self, index: usize, value: T) -> Result<T, IndexOutOfBounds> {
+        MutFoldListSlice::try_set_at(&mut &mut *self, index, value)
+    }
+    /// An alias of [`try_insert_at`](MutFoldListSlice::try_insert_at).
+    pub fn try_insert_at(&mut self, index: usize, value: T) -> Result<(), IndexOutOfBounds> {
+        MutFoldListSlice::try_insert_at(&mut &mut *self, index, value)
+    }
+    /// An alias of [`try_remove_at`](MutFoldListSlice::try_remove_at).
+    pub fn try_remove_at(&mut self, index: usize) -> Result<T, IndexOutOfBounds> {
+        MutFoldListSlice::try_remove_at(&mut &mut *self, index)
+    }
     /// An alias of [`foreach_mut`](MutFoldListSlice::foreach_mut).
     pub fn foreach_mut(&mut self, f: impl FnMut(&mut T)) {
         self.underlying.foreach_mut(f);
@@ -385,6 +636,14 @@ impl<T, D: Clone, Settings: FoldSettings<T,D>> FoldList<T, D, Settings> {
     pub fn append_all_left(&mut self, list: FoldList<T,D,Settings>) {
         self.underlying.append_all_left(list.underlying);
     }
+    /// An alias of [`mut_split_at`](MutFoldListSlice::mut_split_at).
+    pub fn mut_split_at(&mut self, index: usize) -> MutSplit<'_,T,D,&mut Self> {
+        MutFoldListSlice::mut_split_at(self, index)
+    }
+    /// An alias of [`mut_split_where`](MutFoldListSlice::mut_split_where).
+    pub fn mut_split_where(&mut self, predicate: impl Fn(&D)->bool) -> MutSplit<'_,T,D,&mut Self> {
+        MutFoldListSlice::mut_split_where(self, predicate)
+    }
     /// An alias of [`append_left_from_iter`](MutFoldListSlice::append_left_from_iter).
     pub fn append_left_from_iter(&mut self, iter: impl Iterator<Item=T>) {
         self.underlying.append_left_from_iter(iter);
@@ -395,6 +654,37 @@ impl<T, D: Clone, Settings: FoldSettings<T,D>> FoldList<T, D, Settings> {
     }
 }
 
+impl<T, D: Clone, Settings: InvertibleFoldSettings<T,D>> FoldList<T, D, Settings> {
+    /// Get the fold of the elements in `range`, as the difference of two [prefix folds](FoldList::prefix_fold). This is *O*(log(n)), and calls `subtract` once.
+    /// 
+    /// Each prefix fold still calls `op` along its path to the root, so this does about as much work as folding a view of `range`.
+    /// 
+    /// Panics if `range` is out of bounds.
+    pub fn fold_range_by_prefixes(&self, range: impl RangeBounds<usize>) -> D {
+        let (start, end) = range_to_start_end(range, self.len());
+        self.get_settings().subtract(self.prefix_fold(end), self.prefix_fold(start))
+    }
+
+    /// Get the folds of the elements in each of `ranges`, in order, from one descent of this list's tree to all of their endpoints. This is *O*(k log(n)),
+    /// where k is the number of ranges, and calls `subtract` once per range.
+    /// 
+    /// The descent visits each node on the paths to the endpoints once, even when several endpoints share it.
+    /// 
+    /// Panics if any of `ranges` is out of bounds.
+    pub fn fold_ranges<R: RangeBounds<usize>>(&self, ranges: impl IntoIterator<Item = R>) -> Vec<D> {
+        let settings = self.get_settings();
+        let len = self.len();
+        let ranges: Vec<(usize,usize)> = ranges.into_iter().map(|range| range_to_start_end(range, len)).collect();
+        let mut endpoints: Vec<usize> = ranges.iter().flat_map(|&(start,end)| [start,end]).collect();
+        endpoints.sort_unstable();
+        endpoints.dedup();
+        //prefixes[i] is the fold of the first endpoints[i] elements
+        let prefixes = self.underlying.prefix_folds_at(&endpoints);
+        let prefix = |i: usize| prefixes[endpoints.binary_search(&i).unwrap()].1.clone();
+        ranges.into_iter().map(|(start,end)| settings.subtract(prefix(end), prefix(start))).collect()
+    }
+}
+
 /// The trait for views into a [`FoldList`].
 /// 
 /// For views which are also mutable, see [`MutFoldListSlice`].
@@ -802,6 +1092,27 @@ pub trait FoldListSlice<'a,T: 'a, D: Clone + 'a> : 'a + Sized + core::ops::Index
         }
     }
 
+    /// Restrict this view to the elements whose indices are in `range`, like slicing a `Vec` with `&v[range]`.
+    /// 
+    /// This is the same as [`view_drop_left(start)`](FoldListSlice::view_drop_left) followed by [`view_take_left(end - start)`](FoldListSlice::view_take_left).
+    /// 
+    /// Panics if `range` doesn't fit in ```0..=self.len()```, or if its start is greater than its end.
+    /// 
+    /// This operation's mutable version is [`mut_view_range`](MutFoldListSlice::mut_view_range).
+    fn view_range(self, range: impl RangeBounds<usize>) -> FoldListSliceFrom<'a, T, Self::OriginalD, Self::Settings, Self::Simplification, ImmFoldChainSliceStruct<'a, <<<Self::UnderlyingChain as FoldChainSlice<'a, T, (usize, Self::OriginalD)>>::IsReversed as Bool>::Not as Bool>::Not, <<<Self::UnderlyingChain as FoldChainSlice<'a, T, (usize, Self::OriginalD)>>::IsFlushLeft as Bool>::And<<Self::UnderlyingChain as FoldChainSlice<'a, T, (usize, Self::OriginalD)>>::IsReversed> as Bool>::And<<<Self::UnderlyingChain as FoldChainSlice<'a, T, (usize, Self::OriginalD)>>::IsReversed as Bool>::Not>, <<<Self::UnderlyingChain as FoldChainSlice<'a, T, (usize, Self::OriginalD)>>::IsFlushRight as Bool>::And<<<Self::UnderlyingChain as FoldChainSlice<'a, T, (usize, Self::OriginalD)>>::IsReversed as Bool>::Not> as Bool>::And<<<<Self::UnderlyingChain as FoldChainSlice<'a, T, (usize, Self::OriginalD)>>::IsReversed as Bool>::Not as Bool>::Not>, SettingsWithSize<Self::Settings>, (), T, (usize, Self::OriginalD)>> {
+        let (start, end) = range_to_start_end(range, self.len());
+        self.view_drop_left(start).view_take_left(end - start)
+    }
+
+    /// Restrict this view to the elements whose indices are in `range`, like [`view_range`](FoldListSlice::view_range),
+    /// or return [`None`] if `range` doesn't fit in ```0..=self.len()```, or if its start is greater than its end.
+    /// 
+    /// This operation's mutable version is [`try_mut_view_range`](MutFoldListSlice::try_mut_view_range).
+    fn try_view_range(self, range: impl RangeBounds<usize>) -> Option<FoldListSliceFrom<'a, T, Self::OriginalD, Self::Settings, Self::Simplification, ImmFoldChainSliceStruct<'a, <<<Self::UnderlyingChain as FoldChainSlice<'a, T, (usize, Self::OriginalD)>>::IsReversed as Bool>::Not as Bool>::Not, <<<Self::UnderlyingChain as FoldChainSlice<'a, T, (usize, Self::OriginalD)>>::IsFlushLeft as Bool>::And<<Self::UnderlyingChain as FoldChainSlice<'a, T, (usize, Self::OriginalD)>>::IsReversed> as Bool>::And<<<Self::UnderlyingChain as FoldChainSlice<'a, T, (usize, Self::OriginalD)>>::IsReversed as Bool>::Not>, <<<Self::UnderlyingChain as FoldChainSlice<'a, T, (usize, Self::OriginalD)>>::IsFlushRight as Bool>::And<<<Self::UnderlyingChain as FoldChainSlice<'a, T, (usize, Self::OriginalD)>>::IsReversed as Bool>::Not> as Bool>::And<<<<Self::UnderlyingChain as FoldChainSlice<'a, T, (usize, Self::OriginalD)>>::IsReversed as Bool>::Not as Bool>::Not>, SettingsWithSize<Self::Settings>, (), T, (usize, Self::OriginalD)>>> {
+        let (start, end) = try_range_to_start_end(range, self.len())?;
+        Some(self.view_drop_left(start).view_take_left(end - start))
+    }
+
     /// Get a reversed version of this view. See [Reverse](crate#reverse).
     /// 
     /// This operation's mutable version is [`mut_view_reversed`](MutFoldListSlice::mut_view_reversed).
@@ -878,6 +1189,14 @@ pub trait FoldListSlice<'a,T: 'a, D: Clone + 'a> : 'a + Sized + core::ops::Index
         self.borrow().as_unsized_chain_keeping_simplification().fold()
     }
 
+    /// Get this slice's fold, as the difference of two prefix folds of the base [`FoldList`], like [`fold_range_by_prefixes`](FoldList::fold_range_by_prefixes). This is *O*(log(n)), and calls `subtract` once.
+    /// 
+    /// This is the same as [`fold`](FoldListSlice::fold), and does about as much work, since each prefix fold still calls `op` along its path to the root.
+    fn fold_by_prefixes(&self) -> D where Self::Settings: InvertibleFoldSettings<T,Self::OriginalD> {
+        let (_, d) = self.borrow().as_sized_chain().unsimplified_fold_by_prefixes();
+        self.get_current_simplification().simplify(&d)
+    }
+
     /// Get this slice's current number of elements.
     /// 
     /// Note that this is *O*(log(n)), because it's just calling [`fold`](FoldListSlice::fold) under the hood (after a simplification).
@@ -924,8 +1243,39 @@ pub trait FoldListSlice<'a,T: 'a, D: Clone + 'a> : 'a + Sized + core::ops::Index
     /// 
     /// This can also be accomplished via ```&self[index]```.
     fn get<'b>(&'b self, index: usize) -> &'b T where 'a:'b{
+        self.try_get(index).unwrap_or_else(|e| panic!("{}",e))
+    }
+
+    /// Get a reference to the element at index `index`, or an [`IndexOutOfBounds`] error if there is none.
+    /// 
+    /// This is the non-panicking version of [`get`](FoldListSlice::get).
+    fn try_get<'b>(&'b self, index: usize) -> Result<&'b T, IndexOutOfBounds> where 'a:'b{
         self.borrow().view_drop_left(index).underlying.left_consume()
-            .unwrap_or_else(|| panic!("Index out of bounds: the index is {} but the length is {}",index,self.len()))
+            .ok_or_else(|| IndexOutOfBounds { index, len: self.len() })
+    }
+
+    /// Find the first element from the left whose inclusion causes the fold from the left to satisfy `predicate`, in *O*(log(n)).
+    /// 
+    /// Returns the element's index, a reference to it, and the fold of every element to its left, or [`None`] if the fold of this whole slice doesn't satisfy `predicate`.
+    /// This is the element right after the end of [`view_take_left_until(predicate)`](FoldListSlice::view_take_left_until), so its index is that view's length and the returned fold is that view's fold.
+    /// 
+    /// The given `predicate` must be well-behaved; see [Slicing](crate#slicing) for examples.
+    /// 
+    /// Its mirror image is [`find_right`](FoldListSlice::find_right).
+    fn find_left<'b>(&'b self, predicate: impl Fn(&D)->bool) -> Option<(usize, &'b T, D)> where 'a:'b {
+        foldlist_find_left_impl(self.borrow(), predicate)
+    }
+
+    /// Find the first element from the right whose inclusion causes the fold from the right to satisfy `predicate`, in *O*(log(n)).
+    /// 
+    /// Returns the element's index (counted from the left, as usual), a reference to it, and the fold of every element to its right, 
+    /// or [`None`] if the fold of this whole slice doesn't satisfy `predicate`.
+    /// 
+    /// The given `predicate` must be well-behaved; see [Slicing](crate#slicing) for examples.
+    /// 
+    /// Its mirror image is [`find_left`](FoldListSlice::find_left).
+    fn find_right<'b>(&'b self, predicate: impl Fn(&D)->bool) -> Option<(usize, &'b T, D)> where 'a:'b {
+        foldlist_find_right_impl(self.borrow(), predicate)
     }
 
     /// Run a closure for each of this slice's elements, from left to right.
@@ -1326,6 +1676,27 @@ pub trait MutFoldListSlice<'a,T: 'a,D: Clone + 'a>: FoldListSlice<'a,T,D> where
         }
     }
 
+    /// Restrict this view to the elements whose indices are in `range`, like slicing a `Vec` with `&mut v[range]`.
+    /// 
+    /// This is the same as [`mut_view_drop_left(start)`](MutFoldListSlice::mut_view_drop_left) followed by [`mut_view_take_left(end - start)`](MutFoldListSlice::mut_view_take_left).
+    /// 
+    /// Panics if `range` doesn't fit in ```0..=self.len()```, or if its start is greater than its end.
+    /// 
+    /// This operation's immutable version is [`view_range`](FoldListSlice::view_range).
+    fn mut_view_range(self, range: impl RangeBounds<usize>) -> FoldListSliceFrom<'a, T, Self::OriginalD, Self::Settings, Self::Simplification, MutFoldChainSliceStruct<'a, <<<Self::UnderlyingChain as FoldChainSlice<'a, T, (usize, Self::OriginalD)>>::IsReversed as Bool>::Not as Bool>::Not, <<<Self::UnderlyingChain as FoldChainSlice<'a, T, (usize, Self::OriginalD)>>::IsFlushLeft as Bool>::And<<Self::UnderlyingChain as FoldChainSlice<'a, T, (usize, Self::OriginalD)>>::IsReversed> as Bool>::And<<<Self::UnderlyingChain as FoldChainSlice<'a, T, (usize, Self::OriginalD)>>::IsReversed as Bool>::Not>, <<<Self::UnderlyingChain as FoldChainSlice<'a, T, (usize, Self::OriginalD)>>::IsFlushRight as Bool>::And<<<Self::UnderlyingChain as FoldChainSlice<'a, T, (usize, Self::OriginalD)>>::IsReversed as Bool>::Not> as Bool>::And<<<<Self::UnderlyingChain as FoldChainSlice<'a, T, (usize, Self::OriginalD)>>::IsReversed as Bool>::Not as Bool>::Not>, T, (usize, Self::OriginalD), SettingsWithSize<Self::Settings>, ()>> {
+        let (start, end) = range_to_start_end(range, self.len());
+        self.mut_view_drop_left(start).mut_view_take_left(end - start)
+    }
+
+    /// Restrict this view to the elements whose indices are in `range`, like [`mut_view_range`](MutFoldListSlice::mut_view_range),
+    /// or return [`None`] if `range` doesn't fit in ```0..=self.len()```, or if its start is greater than its end.
+    /// 
+    /// This operation's immutable version is [`try_view_range`](FoldListSlice::try_view_range).
+    fn try_mut_view_range(self, range: impl RangeBounds<usize>) -> Option<FoldListSliceFrom<'a, T, Self::OriginalD, Self::Settings, Self::Simplification, MutFoldChainSliceStruct<'a, <<<Self::UnderlyingChain as FoldChainSlice<'a, T, (usize, Self::OriginalD)>>::IsReversed as Bool>::Not as Bool>::Not, <<<Self::UnderlyingChain as FoldChainSlice<'a, T, (usize, Self::OriginalD)>>::IsFlushLeft as Bool>::And<<Self::UnderlyingChain as FoldChainSlice<'a, T, (usize, Self::OriginalD)>>::IsReversed> as Bool>::And<<<Self::UnderlyingChain as FoldChainSlice<'a, T, (usize, Self::OriginalD)>>::IsReversed as Bool>::Not>, <<<Self::UnderlyingChain as FoldChainSlice<'a, T, (usize, Self::OriginalD)>>::IsFlushRight as Bool>::And<<<Self::UnderlyingChain as FoldChainSlice<'a, T, (usize, Self::OriginalD)>>::IsReversed as Bool>::Not> as Bool>::And<<<<Self::UnderlyingChain as FoldChainSlice<'a, T, (usize, Self::OriginalD)>>::IsReversed as Bool>::Not as Bool>::Not>, T, (usize, Self::OriginalD), SettingsWithSize<Self::Settings>, ()>>> {
+        let (start, end) = try_range_to_start_end(range, self.len())?;
+        Some(self.mut_view_drop_left(start).mut_view_take_left(end - start))
+    }
+
     /// Get a reversed version of this view. See [Reverse](crate#reverse).
     /// 
     /// This operation's immutable version is [`view_reversed`](FoldListSlice::view_reversed).
@@ -1476,30 +1847,60 @@ pub trait MutFoldListSlice<'a,T: 'a,D: Clone + 'a>: FoldListSlice<'a,T,D> where
     /// Mutate the element at index `index` via a closure, and return the result of the closure.
     /// 
     /// Panics if `index` is out of bounds.
+    /// 
+    /// For the non-panicking version, see [`try_update_at`](MutFoldListSlice::try_update_at).
     fn update_at<R>(&mut self, index: usize, f: impl FnOnce(&mut T)->R)->R {
+        self.try_update_at(index, f).unwrap_or_else(|e| panic!("{}",e))
+    }
+
+    /// Mutate the element at index `index` via a closure, and return the result of the closure.
+    /// 
+    /// If `index` is out of bounds, this does nothing and returns an [`IndexOutOfBounds`] error.
+    fn try_update_at<R>(&mut self, index: usize, f: impl FnOnce(&mut T)->R) -> Result<R, IndexOutOfBounds> {
         let ret = self.borrow_mut().mut_view_drop_left(index).update_left(|t| t.map(f));
-        ret.unwrap_or_else(|| panic!("Index out of bounds: the index is {} but the length is {}",index,self.len()))
+        ret.ok_or_else(|| IndexOutOfBounds { index, len: self.len() })
     }
 
     /// Replace the element at index `index` with `value` and return the previous value.
     /// 
     /// Panics if `index` is out of bounds.
+    /// 
+    /// For the non-panicking version, see [`try_set_at`](MutFoldListSlice::try_set_at).
     fn set_at(&mut self, index: usize, value: T) -> T {
         self.update_at(index, |t| core::mem::replace(t,value))
     }
 
+    /// Replace the element at index `index` with `value` and return the previous value.
+    /// 
+    /// If `index` is out of bounds, this does nothing and returns an [`IndexOutOfBounds`] error.
+    fn try_set_at(&mut self, index: usize, value: T) -> Result<T, IndexOutOfBounds> {
+        self.try_update_at(index, |t| core::mem::replace(t,value))
+    }
+
     /// Insert `value` at index `index`.
     /// 
     /// The elements whose indices were `index` and above will have their indices shifted up by one.
     /// 
     /// Panics if `index` is outside ```0..=self.len()```.
+    /// 
+    /// For the non-panicking version, see [`try_insert_at`](MutFoldListSlice::try_insert_at).
     fn insert_at(&mut self, index: usize, value: T) {
+        self.try_insert_at(index, value).unwrap_or_else(|e| panic!("{}",e))
+    }
+
+    /// Insert `value` at index `index`.
+    /// 
+    /// The elements whose indices were `index` and above will have their indices shifted up by one.
+    /// 
+    /// If `index` is outside ```0..=self.len()```, this does nothing and returns an [`IndexOutOfBounds`] error.
+    fn try_insert_at(&mut self, index: usize, value: T) -> Result<(), IndexOutOfBounds> {
         let mut slice = self.borrow_mut().mut_view_take_left(index);
         if slice.len() != index {
             drop(slice);
-            panic!("Index out of bounds: the index is {} but the length is {}",index,self.len());
+            return Err(IndexOutOfBounds { index, len: self.len() });
         }
         slice.append_right(value);
+        Ok(())
     }
 
     /// Remove and return the element at index `index`.
@@ -1507,14 +1908,24 @@ pub trait MutFoldListSlice<'a,T: 'a,D: Clone + 'a>: FoldListSlice<'a,T,D> where
     /// The elements that were to the right of it will have their indices shifted down by one.
     /// 
     /// Panics if `index` is out of bounds.
+    /// 
+    /// For the non-panicking version, see [`try_remove_at`](MutFoldListSlice::try_remove_at).
     fn remove_at(&mut self, index: usize) -> T {
-        let mut slice = self.borrow_mut().mut_view_take_left(index + 1);
-        if slice.len() != index + 1 {
-            drop(slice);
-            panic!("Index out of bounds: the index is {} but the length is {}",index,self.len())
+        self.try_remove_at(index).unwrap_or_else(|e| panic!("{}",e))
+    }
+
+    /// Remove and return the element at index `index`.
+    /// 
+    /// The elements that were to the right of it will have their indices shifted down by one.
+    /// 
+    /// If `index` is out of bounds, this does nothing and returns an [`IndexOutOfBounds`] error.
+    fn try_remove_at(&mut self, index: usize) -> Result<T, IndexOutOfBounds> {
+        let len = self.len();
+        if index >= len {
+            return Err(IndexOutOfBounds { index, len });
         }
-        let Some(ret) = slice.pop_right() else {unreachable!()};
-        ret
+        let Some(ret) = self.borrow_mut().mut_view_take_left(index + 1).pop_right() else {unreachable!()};
+        Ok(ret)
     }
 
     /// Run a closure on each of this slice's elements, possibly mutating them, from left to right.
@@ -1564,6 +1975,31 @@ pub trait MutFoldListSlice<'a,T: 'a,D: Clone + 'a>: FoldListSlice<'a,T,D> where
         self.borrow_mut().as_sized_chain().append_all_left(list.underlying);
     }
 
+    /// Split this slice into two halves at index `index`, which can then be mutated independently of each other. See [`MutSplit`].
+    /// 
+    /// The halves are put back into this slice, in order, when the returned [`MutSplit`] is dropped. Both the split and the rejoin are *O*(log(n)).
+    /// 
+    /// Panics if `index` is outside ```0..=self.len()```.
+    fn mut_split_at(mut self, index: usize) -> MutSplit<'a,T,D,Self> {
+        let len = self.len();
+        if index > len {
+            panic!("Index out of bounds: the index is {} but the length is {}",index,len);
+        }
+        let is_reversed = <<Self::UnderlyingChain as FoldChainSlice<'a,T,(usize,Self::OriginalD)>>::IsReversed as Bool>::b;
+        let mut taken = self.take_all();
+        let second = taken.split_off(if is_reversed {len - index} else {index});
+        let (left,right) = if is_reversed {(second,taken)} else {(taken,second)};
+        MutSplit { left, right, slice: self, _m: PhantomData }
+    }
+
+    /// Split this slice into two halves at the first element which causes the fold from the left to satisfy `predicate`, like [`mut_split_at`](MutFoldListSlice::mut_split_at).
+    /// 
+    /// That element will be the leftmost element of the right half. `predicate` should be monotonic, as in [`view_take_left_until`](FoldListSlice::view_take_left_until).
+    fn mut_split_where(self, predicate: impl Fn(&D)->bool) -> MutSplit<'a,T,D,Self> {
+        let index = self.borrow().view_take_left_until(predicate).len();
+        self.mut_split_at(index)
+    }
+
     /// Append every element from an iterator to the left of this slice.
     /// 
     /// This is faster than [`append_left`](MutFoldListSlice::append_left)ing them one-by-one, but not asymptotically faster.
@@ -1579,10 +2015,87 @@ pub trait MutFoldListSlice<'a,T: 'a,D: Clone + 'a>: FoldListSlice<'a,T,D> where
     }
 }
 
+/// Two disjoint halves of a mutable slice of a [`FoldList`], created by [`mut_split_at`](MutFoldListSlice::mut_split_at) or [`mut_split_where`](MutFoldListSlice::mut_split_where).
+/// 
+/// While a `MutSplit` exists, its slice's elements are held in two separate [`FoldList`]s, [`left`](MutSplit::left) and [`right`](MutSplit::right),
+/// so that both halves can be mutated (and folded) at the same time. When it's dropped, the halves are joined and put back into the slice in *O*(log(n)).
+/// 
+/// Each half keeps its elements in the same order as the slice's base `FoldList` (so, for a reversed slice, `left` holds its elements in reverse), 
+/// and the halves' folds are those of the base, without the slice's [simplification](crate#simplification).
+pub struct MutSplit<'a,T: 'a,D: Clone + 'a,Slice: MutFoldListSlice<'a,T,D>> where Slice::UnderlyingChain: MutFoldChainSlice<'a,T,(usize,Slice::OriginalD)> {
+    /// The elements of the slice that were left of the split
+    pub left: FoldList<T,Slice::OriginalD,Slice::Settings>,
+    /// The elements of the slice that were right of the split
+    pub right: FoldList<T,Slice::OriginalD,Slice::Settings>,
+    slice: Slice,
+    _m: PhantomData<(&'a T, D)>
+}
+
+impl<'a,T: 'a,D: Clone + 'a,Slice: MutFoldListSlice<'a,T,D>> Drop for MutSplit<'a,T,D,Slice> where Slice::UnderlyingChain: MutFoldChainSlice<'a,T,(usize,Slice::OriginalD)> {
+    fn drop(&mut self) {
+        let settings = self.slice.get_settings();
+        let left = core::mem::replace(&mut self.left, FoldList::from_settings(settings));
+        let right = core::mem::replace(&mut self.right, FoldList::from_settings(settings));
+        let is_reversed = <<Slice::UnderlyingChain as FoldChainSlice<'a,T,(usize,Slice::OriginalD)>>::IsReversed as Bool>::b;
+        let (mut first,second) = if is_reversed {(right,left)} else {(left,right)};
+        first.append_all_right(second);
+        self.slice.append_all_right(first);
+    }
+}
+
+//turns a range of indices into a start and an end, panicking with the same messages as insert_at if it doesn't fit in 0..=len
+pub(crate) fn range_to_start_end(range: impl RangeBounds<usize>, len: usize) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.checked_add(1).unwrap_or_else(|| panic!("Index out of bounds: the index is {} but the length is {}",start,len)),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.checked_add(1).unwrap_or_else(|| panic!("Index out of bounds: the index is {} but the length is {}",end,len)),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    if start > end {
+        panic!("Invalid range: the range starts at {} but ends at {}",start,end);
+    }
+    if end > len {
+        panic!("Index out of bounds: the index is {} but the length is {}",end,len);
+    }
+    (start, end)
+}
+
+//like range_to_start_end, but returns None instead of panicking
+pub(crate) fn try_range_to_start_end(range: impl RangeBounds<usize>, len: usize) -> Option<(usize, usize)> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.checked_add(1)?,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.checked_add(1)?,
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    (start <= end && end <= len).then_some((start, end))
+}
+
 fn foldlist_index_impl<'a,T: 'a,D: Clone + 'a>(foldlist: impl FoldListSlice<'a,T,D>, index: usize) -> &'a T {
     foldlist.view_drop_left(index).underlying.left_consume().unwrap_or_else(|| panic!("Index out of bounds: index is {}",index))
 }
 
+fn foldlist_find_left_impl<'a,T: 'a,D: Clone + 'a>(foldlist: impl FoldListSlice<'a,T,D>, predicate: impl Fn(&D)->bool) -> Option<(usize, &'a T, D)> {
+    foldlist.as_sized_chain_keeping_simplification()
+        .find_left_consume(|(_,d)| predicate(d))
+        .map(|(t,(n,d))| (n,t,d))
+}
+
+fn foldlist_find_right_impl<'a,T: 'a,D: Clone + 'a>(foldlist: impl FoldListSlice<'a,T,D>, predicate: impl Fn(&D)->bool) -> Option<(usize, &'a T, D)> {
+    let len = foldlist.len();
+    foldlist.as_sized_chain_keeping_simplification()
+        .find_right_consume(|(_,d)| predicate(d))
+        .map(|(t,(n,d))| (len - 1 - n,t,d))
+}
+
 impl<'a, T: 'a, D: Clone + 'a, Settings: FoldSettings<T,D> + 'a> FoldListSlice<'a,T,D> for &'a FoldList<T, D, Settings> {
     type OriginalD = D;
     type Simplification = ();
@@ -1784,14 +2297,14 @@ MutFoldListSlice<'a,T,Simplification::D2> for FoldListSliceFrom<'a, T, D, Settin
 }
 
 impl<T: core::fmt::Debug, D: Clone, Settings: FoldSettings<T,D>> core::fmt::Debug for FoldList<T, D, Settings> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_list().entries(self.iter()).finish()
     }
 }
 
 impl<'a, T: 'a, D: Clone + 'a, Settings: FoldSettings<T,D> + 'a, Simplification: FoldSimplification<T,D> + 'a, Slice: FoldChainSlice<'a,T,(usize,D),Simplification = (), OriginalD = (usize,D),Settings=SettingsWithSize<Settings>> + core::fmt::Debug> 
 core::fmt::Debug for FoldListSliceFrom<'a, T, D, Settings, Simplification, Slice> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         self.underlying.fmt(f)
     }
 }
@@ -1859,6 +2372,120 @@ core::ops::Index<usize> for FoldListSliceFrom<'a, T, D, Settings, Simplification
     }
 }
 
+//comparisons and hashing look at the elements in order, like for slices
+
+impl<T: PartialEq<U>, U, D: Clone, D2: Clone, Settings: FoldSettings<T,D>, Settings2: FoldSettings<U,D2>> 
+PartialEq<FoldList<U, D2, Settings2>> for FoldList<T, D, Settings> {
+    fn eq(&self, other: &FoldList<U, D2, Settings2>) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: PartialEq<U>, U, D: Clone, Settings: FoldSettings<T,D>> 
+PartialEq<[U]> for FoldList<T, D, Settings> {
+    fn eq(&self, other: &[U]) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: PartialEq<U>, U, D: Clone, Settings: FoldSettings<T,D>> 
+PartialEq<Vec<U>> for FoldList<T, D, Settings> {
+    fn eq(&self, other: &Vec<U>) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq, D: Clone, Settings: FoldSettings<T,D>> Eq for FoldList<T, D, Settings> {}
+
+impl<T: PartialOrd, D: Clone, D2: Clone, Settings: FoldSettings<T,D>, Settings2: FoldSettings<T,D2>> 
+PartialOrd<FoldList<T, D2, Settings2>> for FoldList<T, D, Settings> {
+    fn partial_cmp(&self, other: &FoldList<T, D2, Settings2>) -> Option<core::cmp::Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord, D: Clone, Settings: FoldSettings<T,D>> Ord for FoldList<T, D, Settings> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: core::hash::Hash, D: Clone, Settings: FoldSettings<T,D>> core::hash::Hash for FoldList<T, D, Settings> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        self.iter().for_each(|t| t.hash(state));
+    }
+}
+
+impl<'a, 'b, T: PartialEq<U> + 'a, D: Clone + 'a, Settings: FoldSettings<T,D> + 'a, Simplification: FoldSimplification<T,D> + 'a, Slice: FoldChainSlice<'a,T,(usize,D),Simplification = (), OriginalD = (usize,D),Settings=SettingsWithSize<Settings>>, U: 'b, D2: Clone + 'b, Settings2: FoldSettings<U,D2> + 'b, Simplification2: FoldSimplification<U,D2> + 'b, Slice2: FoldChainSlice<'b,U,(usize,D2),Simplification = (), OriginalD = (usize,D2),Settings=SettingsWithSize<Settings2>>> 
+PartialEq<FoldListSliceFrom<'b, U, D2, Settings2, Simplification2, Slice2>> for FoldListSliceFrom<'a, T, D, Settings, Simplification, Slice> {
+    fn eq(&self, other: &FoldListSliceFrom<'b, U, D2, Settings2, Simplification2, Slice2>) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<'a, T: PartialEq<U> + 'a, D: Clone + 'a, Settings: FoldSettings<T,D> + 'a, Simplification: FoldSimplification<T,D> + 'a, Slice: FoldChainSlice<'a,T,(usize,D),Simplification = (), OriginalD = (usize,D),Settings=SettingsWithSize<Settings>>, U> 
+PartialEq<[U]> for FoldListSliceFrom<'a, T, D, Settings, Simplification, Slice> {
+    fn eq(&self, other: &[U]) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<'a, T: PartialEq<U> + 'a, D: Clone + 'a, Settings: FoldSettings<T,D> + 'a, Simplification: FoldSimplification<T,D> + 'a, Slice: FoldChainSlice<'a,T,(usize,D),Simplification = (), OriginalD = (usize,D),Settings=SettingsWithSize<Settings>>, U> 
+PartialEq<Vec<U>> for FoldListSliceFrom<'a, T, D, Settings, Simplification, Slice> {
+    fn eq(&self, other: &Vec<U>) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<'a, T: Eq + 'a, D: Clone + 'a, Settings: FoldSettings<T,D> + 'a, Simplification: FoldSimplification<T,D> + 'a, Slice: FoldChainSlice<'a,T,(usize,D),Simplification = (), OriginalD = (usize,D),Settings=SettingsWithSize<Settings>>> 
+Eq for FoldListSliceFrom<'a, T, D, Settings, Simplification, Slice> {}
+
+impl<'a, 'b, T: PartialOrd + 'a, D: Clone + 'a, Settings: FoldSettings<T,D> + 'a, Simplification: FoldSimplification<T,D> + 'a, Slice: FoldChainSlice<'a,T,(usize,D),Simplification = (), OriginalD = (usize,D),Settings=SettingsWithSize<Settings>>, D2: Clone + 'b, Settings2: FoldSettings<T,D2> + 'b, Simplification2: FoldSimplification<T,D2> + 'b, Slice2: FoldChainSlice<'b,T,(usize,D2),Simplification = (), OriginalD = (usize,D2),Settings=SettingsWithSize<Settings2>>> 
+PartialOrd<FoldListSliceFrom<'b, T, D2, Settings2, Simplification2, Slice2>> for FoldListSliceFrom<'a, T, D, Settings, Simplification, Slice> {
+    fn partial_cmp(&self, other: &FoldListSliceFrom<'b, T, D2, Settings2, Simplification2, Slice2>) -> Option<core::cmp::Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<'a, T: Ord + 'a, D: Clone + 'a, Settings: FoldSettings<T,D> + 'a, Simplification: FoldSimplification<T,D> + 'a, Slice: FoldChainSlice<'a,T,(usize,D),Simplification = (), OriginalD = (usize,D),Settings=SettingsWithSize<Settings>>> 
+Ord for FoldListSliceFrom<'a, T, D, Settings, Simplification, Slice> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<'a, T: core::hash::Hash + 'a, D: Clone + 'a, Settings: FoldSettings<T,D> + 'a, Simplification: FoldSimplification<T,D> + 'a, Slice: FoldChainSlice<'a,T,(usize,D),Simplification = (), OriginalD = (usize,D),Settings=SettingsWithSize<Settings>>> 
+core::hash::Hash for FoldListSliceFrom<'a, T, D, Settings, Simplification, Slice> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        self.iter().for_each(|t| t.hash(state));
+    }
+}
 
+impl<T, D: Clone, Settings: FoldSettings<T,D> + Default> Default for FoldList<T, D, Settings> {
+    fn default() -> Self {
+        Self::from_settings(Settings::default())
+    }
+}
 
+impl<T, D: Clone, Settings: FoldSettings<T,D> + Default> FromIterator<T> for FoldList<T, D, Settings> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut ret = Self::default();
+        ret.append_right_from_iter(iter.into_iter());
+        ret
+    }
+}
+
+impl<T, D: Clone, Settings: FoldSettings<T,D>> Extend<T> for FoldList<T, D, Settings> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.append_right_from_iter(iter.into_iter());
+    }
+}
 
+impl<'a, T: 'a, D: Clone + 'a, Settings: FoldSettings<T,D> + 'a, Simplification: FoldSimplification<T,D> + 'a, Slice: MutFoldChainSlice<'a,T,(usize,D),Simplification = (), OriginalD = (usize,D),Settings=SettingsWithSize<Settings>>> 
+Extend<T> for FoldListSliceFrom<'a, T, D, Settings, Simplification, Slice> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        MutFoldListSlice::append_right_from_iter(self, iter.into_iter());
+    }
+}
@@ -0,0 +1,443 @@
+use core::{marker::PhantomData, ops::{Bound, RangeBounds}};
+
+use crate::{fold_chain::{FoldChain, FoldChainSlice, ImmFoldChainSliceStruct, Iter, MutFoldChainSlice, MutFoldChainSliceStruct}, fold_settings::{FoldSettings, FoldSettingsStruct}, fold_simplification::{OpFromSettings, SimplificationWithoutShortcut, SizeIgnoreFn}, misc::{EmptyFn, False, True, TupleFun}, sorted_fold_list::SortedSettings};
+
+//a mutable view of everything from some key onwards, whose leftmost element, if any, is the first one with a key at least as large
+type Position<'a,K,V,D,Settings> = MutFoldChainSliceStruct<'a, False, False, True, (K,V), (Option<K>,D), SortedSettings<Settings>, ()>;
+
+/// The type of the views returned by [`FoldMap::range_mut`]: a [`MutFoldChainSlice`] of `(K,V)` pairs, whose fold is that of the map's own `Settings`.
+pub type FoldMapRange<'a,K,V,D,Settings> = MutFoldChainSliceStruct<'a, False, False, False, (K,V), (Option<K>,D), SortedSettings<Settings>,
+    SimplificationWithoutShortcut<(K,V), (Option<K>,D), D, SizeIgnoreFn, OpFromSettings<V,D,Settings>>>;
+
+fn starts_at<K: Ord, D>(bound: Bound<&K>) -> impl Fn(&(Option<K>,D))->bool + '_ {
+    move |(k,_)| match bound {
+        Bound::Included(start) => k.as_ref().is_some_and(|k| k >= start),
+        Bound::Excluded(start) => k.as_ref().is_some_and(|k| k > start),
+        Bound::Unbounded => true,
+    }
+}
+
+fn ends_before<K: Ord, D>(bound: Bound<&K>) -> impl Fn(&(Option<K>,D))->bool + '_ {
+    move |(k,_)| match bound {
+        Bound::Included(end) => k.as_ref().is_some_and(|k| k > end),
+        Bound::Excluded(end) => k.as_ref().is_some_and(|k| k >= end),
+        Bound::Unbounded => false,
+    }
+}
+
+/// An ordered map from keys of type `K` to values of type `V`, stored as a [`FoldChain`] of `(K,V)` pairs sorted by key, so that the fold of any range of keys can be found in *O*(log(n)).
+///
+/// Its API follows that of [`BTreeMap`](alloc::collections::BTreeMap), except that values can't be borrowed mutably, since the folds would need updating afterwards.
+/// Instead, they can be mutated with [`update`](FoldMap::update), [`Entry::and_modify`], or through the views returned by [`range_mut`](FoldMap::range_mut).
+///
+/// `K`'s [`Ord`] implementation should be consistent, as for [`BTreeMap`](alloc::collections::BTreeMap), or else the entries may not be found again.
+pub struct FoldMap<K: Clone, V, D: Clone, Settings: FoldSettings<V,D>> {
+    chain: FoldChain<(K,V), (Option<K>,D), SortedSettings<Settings>>,
+    len: usize,
+}
+
+impl<K: Ord + Clone, V, D: Clone, OP: Fn(D,D)->D + Copy, DeltaOf: Fn(&V)->D + Copy, Empty: Fn()->D + Copy> FoldMap<K,V,D,FoldSettingsStruct<V,D,TupleFun<OP>,DeltaOf,EmptyFn<Empty>>> {
+    /// Create a new empty `FoldMap`, given the closures for [Settings](crate#fold-settings), which see only the values.
+    pub fn new(op: OP, delta_of: DeltaOf, empty_delta_fn: Empty) -> Self {
+        FoldMap::from_settings(FoldSettingsStruct {
+            op_closure: TupleFun(op),
+            t2d_closure: delta_of,
+            empty_closure: EmptyFn(empty_delta_fn),
+            _m: PhantomData,
+        })
+    }
+}
+
+impl<K: Ord + Clone, V, D: Clone, Settings: FoldSettings<V,D>> FoldMap<K, V, D, Settings> {
+    /// Create a new empty `FoldMap`, with the specified [Settings](crate#fold-settings), which see only the values.
+    pub fn from_settings(settings: Settings) -> Self {
+        Self { chain: FoldChain::from_settings(SortedSettings(settings)), len: 0 }
+    }
+
+    /// Get the [Settings](crate#fold-settings) of this map.
+    pub fn get_settings(&self) -> Settings {
+        self.chain.get_settings().0
+    }
+
+    /// Get the `FoldChain` which underlies this map.
+    pub fn as_fold_chain(&self) -> &FoldChain<(K,V), (Option<K>,D), SortedSettings<Settings>> {
+        &self.chain
+    }
+
+    /// Get the number of entries in this map.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if this map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get the fold of every value in this map.
+    pub fn fold(&self) -> D {
+        self.chain.fold().1
+    }
+
+    /// Remove every entry from this map.
+    pub fn clear(&mut self) {
+        self.chain.take_all();
+        self.len = 0;
+    }
+
+    fn position(&mut self, key: &K) -> (Position<'_,K,V,D,Settings>, &mut usize) {
+        (self.chain.mut_view_drop_left_until(starts_at(Bound::Included(key))), &mut self.len)
+    }
+
+    /// Get the entry with key `key`, or [`None`] if there is none.
+    pub fn get_key_value(&self, key: &K) -> Option<(&K, &V)> {
+        match self.chain.view_drop_left_until(starts_at(Bound::Included(key))).left_consume() {
+            Some((k,v)) if k == key => Some((k,v)),
+            _ => None,
+        }
+    }
+
+    /// Get the value with key `key`, or [`None`] if there is none.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.get_key_value(key).map(|(_,v)| v)
+    }
+
+    /// Returns `true` if some entry has key `key`.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get_key_value(key).is_some()
+    }
+
+    /// Get the entry with the smallest key, or [`None`] if this map is empty.
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.chain.left().map(|(k,v)| (k,v))
+    }
+
+    /// Get the entry with the largest key, or [`None`] if this map is empty.
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        self.chain.right().map(|(k,v)| (k,v))
+    }
+
+    /// Insert `value` with key `key`. If there already was a value with that key, it is replaced and returned, but the key itself isn't updated.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.entry(key) {
+            Entry::Occupied(mut entry) => Some(entry.insert(value)),
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+                None
+            },
+        }
+    }
+
+    /// Remove and return the entry with key `key`, or return [`None`] if there is none.
+    pub fn remove_entry(&mut self, key: &K) -> Option<(K, V)> {
+        let (mut position, len) = self.position(key);
+        match position.left() {
+            Some((k,_)) if k == key => {
+                *len -= 1;
+                position.pop_left()
+            },
+            _ => None,
+        }
+    }
+
+    /// Remove and return the value with key `key`, or return [`None`] if there is none.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.remove_entry(key).map(|(_,v)| v)
+    }
+
+    /// Mutate the value with key `key` via a closure, and return the result of the closure, or return [`None`] if there is no such value.
+    pub fn update<R>(&mut self, key: &K, f: impl FnOnce(&mut V)->R) -> Option<R> {
+        let (mut position, _) = self.position(key);
+        position.update_left(|kv| match kv {
+            Some((k,v)) if k == key => Some(f(v)),
+            _ => None,
+        })
+    }
+
+    /// Get the entry with key `key`, for in-place manipulation.
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, D, Settings> {
+        let (position, len) = self.position(&key);
+        match position.left() {
+            Some((k,_)) if *k == key => Entry::Occupied(OccupiedEntry { position, len }),
+            _ => Entry::Vacant(VacantEntry { key, position, len }),
+        }
+    }
+
+    /// Remove and return the entry with the smallest key, or return [`None`] if this map is empty.
+    pub fn pop_first(&mut self) -> Option<(K, V)> {
+        let ret = self.chain.pop_left();
+        self.len -= ret.is_some() as usize;
+        ret
+    }
+
+    /// Remove and return the entry with the largest key, or return [`None`] if this map is empty.
+    pub fn pop_last(&mut self) -> Option<(K, V)> {
+        let ret = self.chain.pop_right();
+        self.len -= ret.is_some() as usize;
+        ret
+    }
+
+    fn range_view(&self, range: impl RangeBounds<K>) -> ImmFoldChainSliceStruct<'_, False, False, False, SortedSettings<Settings>, (), (K,V), (Option<K>,D)> {
+        self.chain
+            .view_drop_left_until(starts_at(range.start_bound()))
+            .view_take_left_until(ends_before(range.end_bound()))
+    }
+
+    /// Get the fold of the values whose keys are in `range`. If `range` is decreasing, this is the fold of no values.
+    pub fn fold_range(&self, range: impl RangeBounds<K>) -> D {
+        self.range_view(range).fold().1
+    }
+
+    /// Iterate over the entries whose keys are in `range`, in order of keys.
+    pub fn range(&self, range: impl RangeBounds<K>) -> Iter<'_, False, (K,V), (Option<K>,D)> {
+        self.range_view(range).iter_consume()
+    }
+
+    /// Get a mutable view of the entries whose keys are in `range`. Its fold is that of `Settings`, so e.g. ```map.range_mut(a..b).fold()``` folds the values with keys from `a` to `b`.
+    ///
+    /// The view can be used to mutate values, or to remove entries from this map.
+    pub fn range_mut(&mut self, range: impl RangeBounds<K>) -> RangeMut<'_, K, V, D, Settings> {
+        let settings = self.get_settings();
+        let view = self.chain
+            .mut_view_drop_left_until(starts_at(range.start_bound()))
+            .mut_view_take_left_until(ends_before(range.end_bound()))
+            .mut_view_simplify(SizeIgnoreFn, OpFromSettings { settings, _m: PhantomData });
+        RangeMut { view, len: &mut self.len }
+    }
+
+    /// Iterate over the entries of this map, in order of keys.
+    pub fn iter(&self) -> Iter<'_, False, (K,V), (Option<K>,D)> {
+        self.chain.iter()
+    }
+
+    /// Iterate over the keys of this map, in order.
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &K> {
+        self.iter().map(|(k,_)| k)
+    }
+
+    /// Iterate over the values of this map, in order of keys.
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &V> {
+        self.iter().map(|(_,v)| v)
+    }
+}
+
+/// A mutable view of the entries of a [`FoldMap`] whose keys are in some range, as returned by [`FoldMap::range_mut`].
+pub struct RangeMut<'a, K: Clone, V, D: Clone, Settings: FoldSettings<V,D>> {
+    view: FoldMapRange<'a,K,V,D,Settings>,
+    len: &'a mut usize,
+}
+
+impl<'a, K: Ord + Clone, V, D: Clone, Settings: FoldSettings<V,D>> RangeMut<'a, K, V, D, Settings> {
+    /// Get the fold of the values in this view.
+    pub fn fold(&self) -> D {
+        self.view.fold()
+    }
+
+    /// Returns `true` if this view has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.view.is_empty()
+    }
+
+    /// Get the entry with the smallest key in this view, or [`None`] if it is empty.
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.view.left().map(|(k,v)| (k,v))
+    }
+
+    /// Get the entry with the largest key in this view, or [`None`] if it is empty.
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        self.view.right().map(|(k,v)| (k,v))
+    }
+
+    /// Remove and return the entry with the smallest key in this view, or return [`None`] if it is empty.
+    pub fn pop_first(&mut self) -> Option<(K, V)> {
+        let ret = self.view.pop_left();
+        *self.len -= ret.is_some() as usize;
+        ret
+    }
+
+    /// Remove and return the entry with the largest key in this view, or return [`None`] if it is empty.
+    pub fn pop_last(&mut self) -> Option<(K, V)> {
+        let ret = self.view.pop_right();
+        *self.len -= ret.is_some() as usize;
+        ret
+    }
+
+    /// Mutate every value in this view via a closure, which is also given the value's key.
+    pub fn foreach_mut(&mut self, mut f: impl FnMut(&K, &mut V)) {
+        self.view.foreach_mut(|(k,v)| f(k,v))
+    }
+
+    /// Remove every entry in this view from the map, and return them as a new `FoldMap`.
+    pub fn take_all(&mut self) -> FoldMap<K, V, D, Settings> {
+        let chain = self.view.take_all();
+        let len = chain.iter().count();
+        *self.len -= len;
+        FoldMap { chain, len }
+    }
+
+    /// Iterate over the entries in this view, in order of keys.
+    pub fn iter(&self) -> Iter<'_, False, (K,V), (Option<K>,D)> {
+        self.view.iter()
+    }
+
+    /// Get the underlying view, e.g. to search it with [`view_drop_left_until`](FoldChainSlice::view_drop_left_until).
+    pub fn as_view(&self) -> &FoldMapRange<'a,K,V,D,Settings> {
+        &self.view
+    }
+}
+
+/// A view into a single entry of a [`FoldMap`], which may be vacant or occupied, as returned by [`FoldMap::entry`].
+pub enum Entry<'a, K: Clone, V, D: Clone, Settings: FoldSettings<V,D>> {
+    /// A vacant entry.
+    Vacant(VacantEntry<'a, K, V, D, Settings>),
+    /// An occupied entry.
+    Occupied(OccupiedEntry<'a, K, V, D, Settings>),
+}
+
+/// A vacant [`Entry`].
+pub struct VacantEntry<'a, K: Clone, V, D: Clone, Settings: FoldSettings<V,D>> {
+    key: K,
+    position: Position<'a,K,V,D,Settings>,
+    len: &'a mut usize,
+}
+
+/// An occupied [`Entry`].
+pub struct OccupiedEntry<'a, K: Clone, V, D: Clone, Settings: FoldSettings<V,D>> {
+    position: Position<'a,K,V,D,Settings>,
+    len: &'a mut usize,
+}
+
+impl<'a, K: Ord + Clone, V, D: Clone, Settings: FoldSettings<V,D>> Entry<'a, K, V, D, Settings> {
+    /// Get this entry's key.
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Vacant(entry) => entry.key(),
+            Entry::Occupied(entry) => entry.key(),
+        }
+    }
+
+    /// Insert `default` if this entry is vacant, and return a reference to its value.
+    pub fn or_insert(self, default: V) -> &'a V {
+        self.or_insert_with(|| default)
+    }
+
+    /// Insert the result of `default` if this entry is vacant, and return a reference to its value.
+    pub fn or_insert_with(self, default: impl FnOnce()->V) -> &'a V {
+        match self {
+            Entry::Vacant(entry) => entry.insert(default()),
+            Entry::Occupied(entry) => entry.into_ref(),
+        }
+    }
+
+    /// Insert `V::default()` if this entry is vacant, and return a reference to its value.
+    pub fn or_default(self) -> &'a V where V: Default {
+        self.or_insert_with(V::default)
+    }
+
+    /// Mutate this entry's value via a closure, if it is occupied.
+    pub fn and_modify(mut self, f: impl FnOnce(&mut V)) -> Self {
+        if let Entry::Occupied(entry) = &mut self {
+            entry.update(f);
+        }
+        self
+    }
+}
+
+impl<'a, K: Ord + Clone, V, D: Clone, Settings: FoldSettings<V,D>> VacantEntry<'a, K, V, D, Settings> {
+    /// Get the key that would be used when inserting through this entry.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Take ownership of the key.
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Insert `value` with this entry's key, and return a reference to it.
+    pub fn insert(mut self, value: V) -> &'a V {
+        self.position.append_left((self.key, value));
+        *self.len += 1;
+        &self.position.as_imm().left_consume().unwrap().1
+    }
+}
+
+impl<'a, K: Ord + Clone, V, D: Clone, Settings: FoldSettings<V,D>> OccupiedEntry<'a, K, V, D, Settings> {
+    /// Get this entry's key.
+    pub fn key(&self) -> &K {
+        &self.position.left().unwrap().0
+    }
+
+    /// Get this entry's value.
+    pub fn get(&self) -> &V {
+        &self.position.left().unwrap().1
+    }
+
+    /// Convert this entry into a reference to its value.
+    pub fn into_ref(self) -> &'a V {
+        &self.position.as_imm().left_consume().unwrap().1
+    }
+
+    /// Mutate this entry's value via a closure, and return the result of the closure.
+    pub fn update<R>(&mut self, f: impl FnOnce(&mut V)->R) -> R {
+        self.position.update_left(|kv| f(&mut kv.unwrap().1))
+    }
+
+    /// Replace this entry's value with `value`, and return the old value.
+    pub fn insert(&mut self, value: V) -> V {
+        self.update(|v| core::mem::replace(v, value))
+    }
+
+    /// Remove this entry from the map, and return its key and value.
+    pub fn remove_entry(mut self) -> (K, V) {
+        *self.len -= 1;
+        self.position.pop_left().unwrap()
+    }
+
+    /// Remove this entry from the map, and return its value.
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+}
+
+impl<K: Clone, V: Clone, D: Clone, Settings: FoldSettings<V,D>> Clone for FoldMap<K, V, D, Settings> {
+    fn clone(&self) -> Self {
+        Self { chain: self.chain.clone(), len: self.len }
+    }
+}
+
+impl<K: core::fmt::Debug + Clone, V: core::fmt::Debug, D: Clone, Settings: FoldSettings<V,D>> core::fmt::Debug for FoldMap<K, V, D, Settings> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map().entries(self.chain.iter().map(|(k,v)| (k,v))).finish()
+    }
+}
+
+impl<K: Ord + Clone, V, D: Clone, Settings: FoldSettings<V,D> + Default> Default for FoldMap<K, V, D, Settings> {
+    fn default() -> Self {
+        Self::from_settings(Settings::default())
+    }
+}
+
+impl<K: Ord + Clone, V, D: Clone, Settings: FoldSettings<V,D> + Default> FromIterator<(K,V)> for FoldMap<K, V, D, Settings> {
+    fn from_iter<I: IntoIterator<Item = (K,V)>>(iter: I) -> Self {
+        let mut ret = Self::default();
+        ret.extend(iter);
+        ret
+    }
+}
+
+impl<K: Ord + Clone, V, D: Clone, Settings: FoldSettings<V,D>> Extend<(K,V)> for FoldMap<K, V, D, Settings> {
+    fn extend<I: IntoIterator<Item = (K,V)>>(&mut self, iter: I) {
+        for (k,v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+impl<'a, K: Ord + Clone, V, D: Clone, Settings: FoldSettings<V,D>> IntoIterator for &'a FoldMap<K, V, D, Settings> {
+    type Item = &'a (K,V);
+    type IntoIter = Iter<'a, False, (K,V), (Option<K>,D)>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
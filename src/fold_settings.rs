@@ -35,6 +35,11 @@ impl<T, D, OP: Fun<(D,D),D> + Copy, T2D: for<'a> Fun<&'a T,D> + Copy, EMPTY: Fun
     }
 }
 impl<T, D, OP: Fun<(D,D),D> + Copy, T2D: for<'a> Fun<&'a T,D> + Copy, EMPTY: Fun<(),D> + Copy> Copy for FoldSettingsStruct<T, D, OP, T2D, EMPTY> {}
+impl<T, D, OP: Fun<(D,D),D> + Copy + Default, T2D: for<'a> Fun<&'a T,D> + Copy + Default, EMPTY: Fun<(),D> + Copy + Default> Default for FoldSettingsStruct<T, D, OP, T2D, EMPTY> {
+    fn default() -> Self {
+        Self { op_closure: OP::default(), t2d_closure: T2D::default(), empty_closure: EMPTY::default(), _m: PhantomData }
+    }
+}
 impl<T, D, OP: Fun<(D,D),D> + Copy, T2D: for<'a> Fun<&'a T,D> + Copy, EMPTY: Fun<(),D> + Copy> FoldSettings<T,D> for FoldSettingsStruct<T, D, OP, T2D, EMPTY> {
     fn op(&self, a: D, b: D) -> D {
         self.op_closure.apply((a,b))
@@ -54,7 +59,7 @@ impl<T, D, OP: Fun<(D,D),D> + Copy, T2D: for<'a> Fun<&'a T,D> + Copy, EMPTY: Fun
 ///   * `op` adds sizes
 ///   * `delta_of` is always `1`, since each element is one element
 ///   * `empty` is always `0`, the additive identity
-#[derive(Clone,Copy)]
+#[derive(Clone,Copy,Default)]
 pub struct SettingsWithSize<S>(pub S);
 impl<T,D: Clone, S: FoldSettings<T,D>> FoldSettings<T,(usize,D)> for SettingsWithSize<S> {
     fn op(&self, (n,a): (usize,D), (m,b): (usize,D)) -> (usize,D) {
@@ -66,4 +71,160 @@ impl<T,D: Clone, S: FoldSettings<T,D>> FoldSettings<T,(usize,D)> for SettingsWit
     fn empty(&self) -> (usize,D) {
         (0,self.0.empty())
     }
-}
\ No newline at end of file
+}
+
+/// An element together with its own delta, as computed by some [`FoldSettings`]. This is the element type used with [`CachedDeltaSettings`].
+/// 
+/// The delta is kept up to date by only allowing the element to be changed via [`set`](CachedDelta::set) and [`update`](CachedDelta::update).
+#[derive(Clone,Debug,PartialEq,Eq,Hash)]
+pub struct CachedDelta<T,D> {
+    value: T,
+    delta: D,
+}
+
+impl<T,D> CachedDelta<T,D> {
+    /// Pair `value` with its delta, according to `settings`.
+    pub fn new(value: T, settings: &impl FoldSettings<T,D>) -> Self {
+        let delta = settings.delta_of(&value);
+        Self { value, delta }
+    }
+
+    /// Get a reference to the element.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Get a reference to the element's cached delta.
+    pub fn delta(&self) -> &D {
+        &self.delta
+    }
+
+    /// Unwrap the element, discarding its cached delta.
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    /// Replace the element with `value` and return the previous element. This calls `delta_of` once.
+    pub fn set(&mut self, value: T, settings: &impl FoldSettings<T,D>) -> T {
+        self.delta = settings.delta_of(&value);
+        core::mem::replace(&mut self.value, value)
+    }
+
+    /// Mutate the element via a closure, and return the result of the closure. This calls `delta_of` once, afterwards, even if the closure panics.
+    pub fn update<R>(&mut self, settings: &impl FoldSettings<T,D>, f: impl FnOnce(&mut T) -> R) -> R {
+        //recalculates the delta when dropped, so that it's still right if f panics
+        struct Guard<'a,T,D,Settings: FoldSettings<T,D>> {
+            cached: &'a mut CachedDelta<T,D>,
+            settings: &'a Settings,
+        }
+        impl<'a,T,D,Settings: FoldSettings<T,D>> Drop for Guard<'a,T,D,Settings> {
+            fn drop(&mut self) {
+                self.cached.delta = self.settings.delta_of(&self.cached.value);
+            }
+        }
+        let guard = Guard { cached: self, settings };
+        f(&mut guard.cached.value)
+    }
+}
+
+/// A wrapper around a [`FoldSettings`] value which stores each element's delta next to it, by replacing its element type, `T`, with [`CachedDelta<T,D>`].
+/// 
+/// This way, `delta_of` is only called when an element is created or changed, rather than whenever its node is touched by a rotation or an update of the folds above it,
+/// which can make a difference when `delta_of` is expensive (e.g. when it builds a `String`). The cached delta still has to be cloned instead, so this is most useful when `D` is cheap to clone.
+/// 
+/// Elements can be created via [`wrap`](CachedDeltaSettings::wrap).
+#[derive(Clone,Copy,Default)]
+pub struct CachedDeltaSettings<S>(pub S);
+
+impl<S> CachedDeltaSettings<S> {
+    /// Pair `value` with its delta, so that it can be inserted into a list using these settings.
+    pub fn wrap<T,D>(&self, value: T) -> CachedDelta<T,D> where S: FoldSettings<T,D> {
+        CachedDelta::new(value, &self.0)
+    }
+}
+
+impl<T,D: Clone, S: FoldSettings<T,D>> FoldSettings<CachedDelta<T,D>,D> for CachedDeltaSettings<S> {
+    fn op(&self, a: D, b: D) -> D {
+        self.0.op(a,b)
+    }
+    fn delta_of(&self, t: &CachedDelta<T,D>) -> D {
+        t.delta.clone()
+    }
+    fn empty(&self) -> D {
+        self.0.empty()
+    }
+}
+
+/// An extension of [`FoldSettings`] for settings whose folds can be "subtracted", such as sums, so that the fold of a range can be computed from two prefix folds.
+/// See [`fold_range_by_prefixes`](crate::fold_list::FoldList::fold_range_by_prefixes), [`fold_ranges`](crate::fold_list::FoldList::fold_ranges) and [`fold_by_prefixes`](crate::fold_list::FoldListSlice::fold_by_prefixes).
+/// 
+/// It is required that `subtract` undo `op` from the left, i.e. that, for any deltas `b` and `c`, `subtract(op(b,c), b)` equals `c`. If this isn't the case, then these folds may be wrong.
+pub trait InvertibleFoldSettings<T,D> : FoldSettings<T,D> {
+    /// Remove `b` from the left of `a`, where `a` is `op(b,c)` for some `c`, and return `c`
+    fn subtract(&self, a: D, b: D) -> D;
+
+    /// Get the inverse of `d`, i.e. the delta `c` such that `op(d,c)` is `empty()`. This is ```subtract(empty(), d)```, so it only works if `d` has an inverse, which e.g. isn't the case for unsigned sums.
+    fn inverse(&self, d: D) -> D {
+        self.subtract(self.empty(), d)
+    }
+}
+
+/// A struct which implements [`InvertibleFoldSettings`] by wrapping a [`FoldSettings`] value along with a closure, which can be [named](crate#nameable-type).
+pub struct InvertibleFoldSettingsStruct<T,D,Settings: FoldSettings<T,D>,Subtract: Fun<(D,D),D> + Copy> {
+    /// The settings used for everything other than subtraction
+    pub settings: Settings,
+
+    /// The closure for [`InvertibleFoldSettings::subtract`]
+    pub subtract_closure: Subtract,
+
+    #[allow(missing_docs)]
+    pub _m: PhantomData<fn(T,D)->D>
+}
+
+impl<T, D, Settings: FoldSettings<T,D>, Subtract: Fun<(D,D),D> + Copy> Clone for InvertibleFoldSettingsStruct<T, D, Settings, Subtract> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T, D, Settings: FoldSettings<T,D>, Subtract: Fun<(D,D),D> + Copy> Copy for InvertibleFoldSettingsStruct<T, D, Settings, Subtract> {}
+impl<T, D, Settings: FoldSettings<T,D> + Default, Subtract: Fun<(D,D),D> + Copy + Default> Default for InvertibleFoldSettingsStruct<T, D, Settings, Subtract> {
+    fn default() -> Self {
+        Self { settings: Settings::default(), subtract_closure: Subtract::default(), _m: PhantomData }
+    }
+}
+impl<T, D, Settings: FoldSettings<T,D>, Subtract: Fun<(D,D),D> + Copy> FoldSettings<T,D> for InvertibleFoldSettingsStruct<T, D, Settings, Subtract> {
+    fn op(&self, a: D, b: D) -> D {
+        self.settings.op(a,b)
+    }
+    fn delta_of(&self, t: &T) -> D {
+        self.settings.delta_of(t)
+    }
+    fn empty(&self) -> D {
+        self.settings.empty()
+    }
+}
+impl<T, D, Settings: FoldSettings<T,D>, Subtract: Fun<(D,D),D> + Copy> InvertibleFoldSettings<T,D> for InvertibleFoldSettingsStruct<T, D, Settings, Subtract> {
+    fn subtract(&self, a: D, b: D) -> D {
+        self.subtract_closure.apply((a,b))
+    }
+}
+
+impl<T,D: Clone, S: InvertibleFoldSettings<T,D>> InvertibleFoldSettings<T,(usize,D)> for SettingsWithSize<S> {
+    fn subtract(&self, (n,a): (usize,D), (m,b): (usize,D)) -> (usize,D) {
+        (n - m, self.0.subtract(a,b))
+    }
+    /// Sizes have no inverses, so this panics unless `d` is the delta of no elements.
+    fn inverse(&self, (n,d): (usize,D)) -> (usize,D) {
+        assert!(n == 0, "Can't invert the delta of {} elements: sizes have no inverse", n);
+        (0, self.0.inverse(d))
+    }
+}
+
+impl<T,D: Clone, S: InvertibleFoldSettings<T,D>> InvertibleFoldSettings<CachedDelta<T,D>,D> for CachedDeltaSettings<S> {
+    fn subtract(&self, a: D, b: D) -> D {
+        self.0.subtract(a,b)
+    }
+    fn inverse(&self, d: D) -> D {
+        self.0.inverse(d)
+    }
+}
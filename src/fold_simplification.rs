@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use core::marker::PhantomData;
 
 use crate::{fold_settings::FoldSettings, misc::{Bool, ComposeRefFn, Fun}};
 
@@ -0,0 +1,272 @@
+use core::{iter::{FusedIterator, Rev}, marker::PhantomData, slice};
+use alloc::vec::Vec;
+
+use crate::{fold_list::FoldList, fold_settings::{FoldSettings, FoldSettingsStruct}, misc::{EmptyFn, TupleFun}};
+
+/// A queue which elements are pushed onto the back of and popped off the front of, like a sliding window over a stream, and which keeps track of the fold of its elements.
+///
+/// [`push_back`](FoldWindow::push_back), [`pop_front`](FoldWindow::pop_front) and [`fold`](FoldWindow::fold) each call `op` and `delta_of` amortized *O*(1) times,
+/// without needing `op` to be invertible. This is faster than a [`FoldList`] for this pattern, since there is no tree to rebalance.
+///
+/// A `FoldWindow` uses the same [Settings](crate#fold-settings) as a `FoldList`, so for anything else, such as slicing, it can be turned into one with [`into_fold_list`](FoldWindow::into_fold_list),
+/// and back with [`from_fold_list`](FoldWindow::from_fold_list), both in *O*(n).
+///
+/// Internally, it is a pair of stacks: the front one holds the fold from each of its elements to its bottom, and the back one holds the fold of all of its elements.
+/// Whenever the front stack runs out, every element of the back stack is moved to it.
+pub struct FoldWindow<T, D: Clone, Settings: FoldSettings<T,D>> {
+    //the front element is last, and each element is paired with the fold from it to the first element
+    front: Vec<(T,D)>,
+    back: Vec<T>,
+    //none exactly when back is empty
+    back_fold: Option<D>,
+    settings: Settings,
+}
+
+impl<T, D: Clone, OP: Fn(D,D)->D + Copy, DeltaOf: Fn(&T)->D + Copy, Empty: Fn()->D + Copy> FoldWindow<T,D,FoldSettingsStruct<T,D,TupleFun<OP>,DeltaOf,EmptyFn<Empty>>> {
+    /// Create a new empty `FoldWindow`, given the closures for [Settings](crate#fold-settings).
+    pub fn new(op: OP, delta_of: DeltaOf, empty_delta_fn: Empty) -> Self {
+        FoldWindow::from_settings(FoldSettingsStruct {
+            op_closure: TupleFun(op),
+            t2d_closure: delta_of,
+            empty_closure: EmptyFn(empty_delta_fn),
+            _m: PhantomData,
+        })
+    }
+}
+
+impl<T, D: Clone, Settings: FoldSettings<T,D>> FoldWindow<T, D, Settings> {
+    /// Create a new empty `FoldWindow`, with the specified [Settings](crate#fold-settings).
+    pub fn from_settings(settings: Settings) -> Self {
+        Self { front: Vec::new(), back: Vec::new(), back_fold: None, settings }
+    }
+
+    /// Create a new `FoldWindow` with the elements of `list`, in the same order, and with the same [Settings](crate#fold-settings). This is *O*(n).
+    pub fn from_fold_list(list: FoldList<T, D, Settings>) -> Self {
+        let mut ret = Self::from_settings(list.get_settings());
+        ret.extend(list);
+        ret
+    }
+
+    /// Turn this window into a [`FoldList`] with the same elements and [Settings](crate#fold-settings), e.g. to take views of arbitrary ranges of it. This is *O*(n).
+    pub fn into_fold_list(self) -> FoldList<T, D, Settings> {
+        let mut ret = FoldList::from_settings(self.settings);
+        ret.append_right_from_iter(self.front.into_iter().rev().map(|(t,_)| t).chain(self.back));
+        ret
+    }
+
+    /// Get the [Settings](crate#fold-settings) of this window.
+    pub fn get_settings(&self) -> Settings {
+        self.settings
+    }
+
+    /// Get the number of elements in this window.
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    /// Returns `true` if this window has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.front.is_empty() && self.back.is_empty()
+    }
+
+    /// Get the fold of every element in this window. This calls `op` at most once.
+    pub fn fold(&self) -> D {
+        match (self.front.last(), &self.back_fold) {
+            (Some((_,d)), Some(back_fold)) => self.settings.op(d.clone(), back_fold.clone()),
+            (Some((_,d)), None) => d.clone(),
+            (None, Some(back_fold)) => back_fold.clone(),
+            (None, None) => self.settings.empty(),
+        }
+    }
+
+    /// Add `value` to the back of this window.
+    pub fn push_back(&mut self, value: T) {
+        let d = self.settings.delta_of(&value);
+        //calculated before replacing the old fold, so that a panicking op leaves the window as it was
+        let back_fold = match &self.back_fold {
+            Some(back_fold) => self.settings.op(back_fold.clone(), d),
+            None => d,
+        };
+        self.back_fold = Some(back_fold);
+        self.back.push(value);
+    }
+
+    /// Remove and return the element at the front of this window, or return [`None`] if it is empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.front.is_empty() {
+            self.move_back_to_front();
+        }
+        self.front.pop().map(|(t,_)| t)
+    }
+
+    //moves every element of the back stack onto the front stack, calculating their folds to the back, which must be empty
+    fn move_back_to_front(&mut self) {
+        debug_assert!(self.front.is_empty());
+        let settings = self.settings;
+        let guard = MoveBackGuard { front: &mut self.front, back: &mut self.back };
+        guard.front.reserve(guard.back.len());
+        let mut acc: Option<D> = None;
+        while let Some(t) = guard.back.last() {
+            let d = settings.delta_of(t);
+            let d = match acc {
+                Some(acc) => settings.op(d, acc),
+                None => d,
+            };
+            acc = Some(d.clone());
+            let t = guard.back.pop().unwrap();
+            guard.front.push((t, d));
+        }
+        core::mem::forget(guard);
+        self.back_fold = None;
+    }
+
+    /// Add `value` to the back of this window, then remove and return elements from the front until there are at most `max_len`.
+    ///
+    /// This is how a window of fixed size slides along a stream; the return value is the element which slid out of it, if there was one.
+    pub fn push_back_bounded(&mut self, value: T, max_len: usize) -> Option<T> {
+        self.push_back(value);
+        let mut ret = None;
+        while self.len() > max_len {
+            ret = self.pop_front();
+        }
+        ret
+    }
+
+    /// Get the element at the front of this window, or [`None`] if it is empty.
+    pub fn front(&self) -> Option<&T> {
+        match self.front.last() {
+            Some((t,_)) => Some(t),
+            None => self.back.first(),
+        }
+    }
+
+    /// Get the element at the back of this window, or [`None`] if it is empty.
+    pub fn back(&self) -> Option<&T> {
+        match self.back.last() {
+            Some(t) => Some(t),
+            None => self.front.first().map(|(t,_)| t),
+        }
+    }
+
+    /// Get the element at index `index` from the front, or [`None`] if `index` is out of bounds. This is *O*(1).
+    pub fn get(&self, index: usize) -> Option<&T> {
+        let front_len = self.front.len();
+        if index < front_len {
+            Some(&self.front[front_len - 1 - index].0)
+        } else {
+            self.back.get(index - front_len)
+        }
+    }
+
+    /// Remove every element from this window.
+    pub fn clear(&mut self) {
+        self.front.clear();
+        self.back.clear();
+        self.back_fold = None;
+    }
+
+    /// Iterate over the elements of this window, from front to back.
+    pub fn iter(&self) -> Iter<'_, T, D> {
+        Iter { front: self.front.iter().rev(), back: self.back.iter() }
+    }
+}
+
+//moves the elements of the front stack back onto the back stack when dropped, so that if op or delta_of panics partway through moving them, no elements are lost and the back stack's fold stays right
+struct MoveBackGuard<'a, T, D> {
+    front: &'a mut Vec<(T,D)>,
+    back: &'a mut Vec<T>,
+}
+
+impl<'a, T, D> Drop for MoveBackGuard<'a, T, D> {
+    fn drop(&mut self) {
+        while let Some((t,_)) = self.front.pop() {
+            self.back.push(t);
+        }
+    }
+}
+
+/// An iterator over the elements of a [`FoldWindow`], from front to back, as returned by [`FoldWindow::iter`].
+pub struct Iter<'a, T, D> {
+    front: Rev<slice::Iter<'a, (T,D)>>,
+    back: slice::Iter<'a, T>,
+}
+
+impl<'a, T, D> Iterator for Iter<'a, T, D> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<&'a T> {
+        match self.front.next() {
+            Some((t,_)) => Some(t),
+            None => self.back.next(),
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len() + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T, D> DoubleEndedIterator for Iter<'a, T, D> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        match self.back.next_back() {
+            Some(t) => Some(t),
+            None => self.front.next_back().map(|(t,_)| t),
+        }
+    }
+}
+
+impl<'a, T, D> ExactSizeIterator for Iter<'a, T, D> {}
+impl<'a, T, D> FusedIterator for Iter<'a, T, D> {}
+
+impl<T: Clone, D: Clone, Settings: FoldSettings<T,D>> Clone for FoldWindow<T, D, Settings> {
+    fn clone(&self) -> Self {
+        Self { front: self.front.clone(), back: self.back.clone(), back_fold: self.back_fold.clone(), settings: self.settings }
+    }
+}
+
+impl<T: core::fmt::Debug, D: Clone, Settings: FoldSettings<T,D>> core::fmt::Debug for FoldWindow<T, D, Settings> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T, D: Clone, Settings: FoldSettings<T,D> + Default> Default for FoldWindow<T, D, Settings> {
+    fn default() -> Self {
+        Self::from_settings(Settings::default())
+    }
+}
+
+impl<T, D: Clone, Settings: FoldSettings<T,D> + Default> FromIterator<T> for FoldWindow<T, D, Settings> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut ret = Self::default();
+        ret.extend(iter);
+        ret
+    }
+}
+
+impl<T, D: Clone, Settings: FoldSettings<T,D>> Extend<T> for FoldWindow<T, D, Settings> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for t in iter {
+            self.push_back(t);
+        }
+    }
+}
+
+impl<'a, T, D: Clone, Settings: FoldSettings<T,D>> IntoIterator for &'a FoldWindow<T, D, Settings> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T, D>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, D: Clone, Settings: FoldSettings<T,D>> From<FoldList<T, D, Settings>> for FoldWindow<T, D, Settings> {
+    fn from(list: FoldList<T, D, Settings>) -> Self {
+        Self::from_fold_list(list)
+    }
+}
+
+impl<T, D: Clone, Settings: FoldSettings<T,D>> From<FoldWindow<T, D, Settings>> for FoldList<T, D, Settings> {
+    fn from(window: FoldWindow<T, D, Settings>) -> Self {
+        window.into_fold_list()
+    }
+}
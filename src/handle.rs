@@ -0,0 +1,93 @@
+use core::{ptr::NonNull, sync::atomic::{AtomicPtr, Ordering}};
+use alloc::sync::{Arc, Weak};
+
+use crate::{fold_chain::{FoldChain, WAVLNode}, fold_list::FoldList, fold_settings::{FoldSettings, SettingsWithSize}};
+
+type Chain<T,D,Settings> = FoldChain<T,(usize,D),SettingsWithSize<Settings>>;
+type NodePtr<T,D> = NonNull<WAVLNode<T,(usize,D)>>;
+
+/// A handle to one element of a [`FoldList`], which keeps referring to that element as other elements are inserted and removed.
+///
+/// Make one with [`FoldList::append_left_with_handle`], [`FoldList::append_right_with_handle`], [`FoldList::insert_at_with_handle`] or [`FoldList::handle_at`].
+/// Given the handle, [`FoldList::index_of`], [`FoldList::prefix_fold_of`], [`FoldList::value_of`], [`FoldList::update_by_handle`] and [`FoldList::remove_by_handle`] are all *O*(log(n)).
+///
+/// Once the element is removed, these all return [`None`]. They also return `None` if the handle is used with a list other than the one its element is in.
+/// The handle follows its element when that element is moved into another list, e.g. by [`append_all_right`](crate::fold_list::MutFoldListSlice::append_all_right) or [`take_all`](crate::fold_list::MutFoldListSlice::take_all),
+/// so it's then valid for that list instead. Replacing the element, e.g. with [`set_at`](crate::fold_list::MutFoldListSlice::set_at), doesn't invalidate the handle.
+///
+/// Handles need the `handles` feature. With it, every element stores a pointer and a count for them, even if it has no handle.
+/// Moving elements between lists, e.g. with [`split_off`](FoldList::split_off) or [`append_all_right`](crate::fold_list::MutFoldListSlice::append_all_right),
+/// takes an extra *O*(log(n)) time per handle on whichever side has fewer elements with handles, since those have to be told which list they're in now.
+/// If either side has no elements with handles, it takes no extra time.
+pub struct Handle<T,D> {
+    node: NodePtr<T,D>,
+    marker: Weak<HandleMarker>,
+}
+
+//send and sync are justified because the node is only ever accessed through a list which it's in, so it's the list's borrow which protects it
+unsafe impl<T,D> Send for Handle<T,D> {}
+unsafe impl<T,D> Sync for Handle<T,D> {}
+
+//shared by a node with handles and every handle to it, and dropped along with the node, which is how handles know that it's gone.
+//owner is the address of one of the tokens of the chain which the node is in (see FoldChain::handle_tokens), and the marker holds a strong count of that token,
+//so that its address isn't reused while the marker points to it
+pub(crate) struct HandleMarker {
+    owner: AtomicPtr<()>,
+}
+
+impl HandleMarker {
+    pub(crate) fn new(owner: &Arc<()>) -> Self {
+        Self { owner: AtomicPtr::new(Arc::into_raw(owner.clone()).cast_mut()) }
+    }
+
+    pub(crate) fn owner(&self) -> usize {
+        self.owner.load(Ordering::Relaxed) as usize
+    }
+
+    //only called while the chain which the node is moving into is borrowed mutably
+    pub(crate) fn set_owner(&self, owner: &Arc<()>) {
+        let old = self.owner.swap(Arc::into_raw(owner.clone()).cast_mut(), Ordering::Relaxed);
+        drop(unsafe { Arc::from_raw(old.cast_const()) });
+    }
+}
+
+impl Drop for HandleMarker {
+    fn drop(&mut self) {
+        drop(unsafe { Arc::from_raw(self.owner.get_mut().cast_const()) });
+    }
+}
+
+impl<T,D: Clone> Handle<T,D> {
+    //node must be in chain
+    pub(crate) unsafe fn new<Settings: FoldSettings<T,D>>(chain: &mut Chain<T,D,Settings>, node: NodePtr<T,D>) -> Self {
+        Self { node, marker: unsafe { chain.handle_marker_of(node) } }
+    }
+
+    //the node of this handle's element, if that element is still in list.
+    //this never touches the node unless it's in list, since otherwise, whichever list it's in could be mutating or dropping it on another thread
+    pub(crate) fn node_in<Settings: FoldSettings<T,D>>(&self, list: &FoldList<T,D,Settings>) -> Option<NodePtr<T,D>> {
+        let marker = self.marker.upgrade()?;
+        //the owner is only ever set to one of list's tokens while list is borrowed mutably, so if it is one, the node is in list, and stays there while list is borrowed
+        list.underlying.owns_handle_marker(&marker).then_some(self.node)
+    }
+}
+
+impl<T,D> Clone for Handle<T,D> {
+    fn clone(&self) -> Self {
+        Self { node: self.node, marker: self.marker.clone() }
+    }
+}
+
+impl<T,D> PartialEq for Handle<T,D> {
+    fn eq(&self, other: &Self) -> bool {
+        Weak::ptr_eq(&self.marker, &other.marker)
+    }
+}
+
+impl<T,D> Eq for Handle<T,D> {}
+
+impl<T,D> core::fmt::Debug for Handle<T,D> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Handle").field("is_alive", &(self.marker.strong_count() != 0)).finish()
+    }
+}
@@ -0,0 +1,172 @@
+use core::fmt::{self, Debug, Display};
+use alloc::vec::Vec;
+
+use crate::{fold_settings::FoldSettings, fold_simplification::FoldSimplification};
+
+/// A counterexample to one of the laws that a [`FoldSettings`] must satisfy, as found by [`check_settings`].
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub enum SettingsLawViolation<D> {
+    /// `op(op(a,b),c)` differs from `op(a,op(b,c))`.
+    NotAssociative {
+        #[allow(missing_docs)]
+        a: D,
+        #[allow(missing_docs)]
+        b: D,
+        #[allow(missing_docs)]
+        c: D,
+        /// `op(op(a,b),c)`
+        left_first: D,
+        /// `op(a,op(b,c))`
+        right_first: D,
+    },
+    /// `op(empty(),a)` differs from `a`.
+    NotLeftIdentity {
+        #[allow(missing_docs)]
+        a: D,
+        /// `op(empty(),a)`
+        result: D,
+    },
+    /// `op(a,empty())` differs from `a`.
+    NotRightIdentity {
+        #[allow(missing_docs)]
+        a: D,
+        /// `op(a,empty())`
+        result: D,
+    },
+}
+
+impl<D: Debug> Display for SettingsLawViolation<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotAssociative { a, b, c, left_first, right_first } =>
+                write!(f, "op is not associative: for a = {:?}, b = {:?}, c = {:?}, op(op(a,b),c) is {:?} but op(a,op(b,c)) is {:?}", a, b, c, left_first, right_first),
+            Self::NotLeftIdentity { a, result } =>
+                write!(f, "empty is not a left identity: for a = {:?}, op(empty(),a) is {:?}", a, result),
+            Self::NotRightIdentity { a, result } =>
+                write!(f, "empty is not a right identity: for a = {:?}, op(a,empty()) is {:?}", a, result),
+        }
+    }
+}
+
+impl<D: Debug> core::error::Error for SettingsLawViolation<D> {}
+
+/// A counterexample to one of the laws that a [`FoldSimplification`] must satisfy, as found by [`check_simplification`].
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub enum SimplificationLawViolation<D,D2> {
+    /// `simplify(op(a,b))` differs from the simplified `op(simplify(a),simplify(b))`.
+    DoesNotCommuteWithOp {
+        #[allow(missing_docs)]
+        a: D,
+        #[allow(missing_docs)]
+        b: D,
+        /// `simplify(op(a,b))`
+        simplified_op: D2,
+        /// The simplified `op(simplify(a),simplify(b))`
+        op_of_simplified: D2,
+    },
+    /// The simplified `empty()` differs from `simplify(empty())`.
+    EmptyMismatch {
+        /// The simplified `empty()`
+        simplified_empty: D2,
+        /// `simplify(empty())`
+        empty_simplified: D2,
+    },
+    /// The simplified `delta_of(samples[index])` differs from `simplify(delta_of(samples[index]))`.
+    DeltaMismatch {
+        /// The index of the sample in question
+        index: usize,
+        /// The simplified `delta_of(samples[index])`
+        simplified_delta: D2,
+        /// `simplify(delta_of(samples[index]))`
+        delta_simplified: D2,
+    },
+}
+
+impl<D: Debug, D2: Debug> Display for SimplificationLawViolation<D,D2> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DoesNotCommuteWithOp { a, b, simplified_op, op_of_simplified } =>
+                write!(f, "the simplification does not commute with op: for a = {:?}, b = {:?}, simplify(op(a,b)) is {:?} but the simplified op(simplify(a),simplify(b)) is {:?}", a, b, simplified_op, op_of_simplified),
+            Self::EmptyMismatch { simplified_empty, empty_simplified } =>
+                write!(f, "the simplified empty is {:?} but simplify(empty()) is {:?}", simplified_empty, empty_simplified),
+            Self::DeltaMismatch { index, simplified_delta, delta_simplified } =>
+                write!(f, "for sample {}, the simplified delta_of is {:?} but simplify(delta_of(sample)) is {:?}", index, simplified_delta, delta_simplified),
+        }
+    }
+}
+
+impl<D: Debug, D2: Debug> core::error::Error for SimplificationLawViolation<D,D2> {}
+
+//the deltas of the samples, plus empty and the folds of neighboring pairs of samples, so that composite values are also tested
+fn generate_deltas<T,D: Clone>(settings: &impl FoldSettings<T,D>, samples: &[T]) -> Vec<D> {
+    let singles: Vec<D> = samples.iter().map(|t| settings.delta_of(t)).collect();
+    let pairs = singles.windows(2).map(|w| settings.op(w[0].clone(), w[1].clone()));
+    core::iter::once(settings.empty()).chain(singles.iter().cloned()).chain(pairs).collect()
+}
+
+/// Check that `settings` satisfies the laws needed for folds to be consistent: `op` must be associative, and `empty` must be a left and right identity of `op`.
+///
+/// The laws are tested on the deltas of `samples`, on `empty()`, and on the folds of each two neighboring samples.
+/// Associativity is tested on every triple of these, so this is *O*(n<sup>3</sup>) for n samples, and only a few dozen samples should be used.
+///
+/// Returns the first counterexample found, if any.
+/// Note that floating-point addition and multiplication are only approximately associative, so they may fail this check.
+pub fn check_settings<T,D: Clone + PartialEq>(settings: impl FoldSettings<T,D>, samples: &[T]) -> Result<(), SettingsLawViolation<D>> {
+    let deltas = generate_deltas(&settings, samples);
+    for a in &deltas {
+        let result = settings.op(settings.empty(), a.clone());
+        if result != *a {
+            return Err(SettingsLawViolation::NotLeftIdentity { a: a.clone(), result });
+        }
+        let result = settings.op(a.clone(), settings.empty());
+        if result != *a {
+            return Err(SettingsLawViolation::NotRightIdentity { a: a.clone(), result });
+        }
+    }
+    for a in &deltas {
+        for b in &deltas {
+            let ab = settings.op(a.clone(), b.clone());
+            for c in &deltas {
+                let left_first = settings.op(ab.clone(), c.clone());
+                let right_first = settings.op(a.clone(), settings.op(b.clone(), c.clone()));
+                if left_first != right_first {
+                    return Err(SettingsLawViolation::NotAssociative { a: a.clone(), b: b.clone(), c: c.clone(), left_first, right_first });
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Check that `simplification` is consistent with `settings`: simplifying must commute with `op`,
+/// and the simplification's `empty` and `delta_of` (which may be shortcuts) must agree with simplifying the original ones.
+///
+/// The laws are tested on the same values as in [`check_settings`], and commuting with `op` is tested on every pair of them, so this is *O*(n<sup>2</sup>) for n samples.
+///
+/// Returns the first counterexample found, if any.
+/// This doesn't check `settings` itself; see [`check_settings`] for that.
+pub fn check_simplification<T,D: Clone,S: FoldSimplification<T,D>>(simplification: S, settings: impl FoldSettings<T,D>, samples: &[T]) -> Result<(), SimplificationLawViolation<D,S::D2>> where S::D2: PartialEq {
+    let simplified_empty = simplification.empty(settings);
+    let empty_simplified = simplification.simplify(&settings.empty());
+    if simplified_empty != empty_simplified {
+        return Err(SimplificationLawViolation::EmptyMismatch { simplified_empty, empty_simplified });
+    }
+    for (index, sample) in samples.iter().enumerate() {
+        let simplified_delta = simplification.delta_of(sample, settings);
+        let delta_simplified = simplification.simplify(&settings.delta_of(sample));
+        if simplified_delta != delta_simplified {
+            return Err(SimplificationLawViolation::DeltaMismatch { index, simplified_delta, delta_simplified });
+        }
+    }
+    let deltas = generate_deltas(&settings, samples);
+    for a in &deltas {
+        for b in &deltas {
+            let simplified_op = simplification.simplify(&settings.op(a.clone(), b.clone()));
+            let op_of_simplified = simplification.op(simplification.simplify(a), simplification.simplify(b), settings);
+            if simplified_op != op_of_simplified {
+                return Err(SimplificationLawViolation::DoesNotCommuteWithOp { a: a.clone(), b: b.clone(), simplified_op, op_of_simplified });
+            }
+        }
+    }
+    Ok(())
+}
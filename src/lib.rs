@@ -1,4 +1,5 @@
 #![warn(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
 
 //! [`FoldList`](fold_list::FoldList) is a list-like data structure, stored as a tree, where the following operations are all *O*(log(n)) in the worst case:
@@ -187,7 +188,7 @@
 //! Unlike [`Vec`], [`FoldList`](fold_list::FoldList) doesn't allow you to borrow direct mutable references to its elements, because if you could, then you could change its folds without it noticing[^c].
 //! So instead of being able to do this:
 //! 
-//! [^c]: You are still technically able to do this, by using a type that lets you mutate it via an immutable reference, such as [`RefCell`](std::cell::RefCell) or an atomic type. But you shouldn't, or else folds might be inaccurate.
+//! [^c]: You are still technically able to do this, by using a type that lets you mutate it via an immutable reference, such as [`RefCell`](core::cell::RefCell) or an atomic type. But you shouldn't, or else folds might be inaccurate.
 //! 
 //! ```
 //! let x = my_vec[10].fn_that_mutates(); 
@@ -408,6 +409,19 @@
 //! Conversely, a `FoldChain` of the proper form can be converted back to a `FoldList` via [`as_fold_list`](fold_chain::FoldChain::as_fold_list).
 //! 
 //! If you never use a `FoldList`'s size information, consider using a `FoldChain` instead.
+//! 
+//! # Cargo features
+//! 
+//! - `std` (enabled by default): Without it, this crate is `#![no_std]`, and only needs [`alloc`].
+//! - `serde`: Serialization of `FoldList`s and `FoldChain`s, in the `serde` module.
+//! - `rayon`: Parallel iteration, building and mutation, in the `rayon` module. Implies `std`.
+//! - `handles`: Handles to elements of a `FoldList`, in the `handle` module, and the `priority_queue` module, which is built on them. Needs pointer-sized atomics.
+//!   Without it, elements don't store anything for handles.
+
+extern crate alloc;
+
+#[cfg(all(feature = "handles", not(target_has_atomic = "ptr")))]
+compile_error!("the `handles` feature needs a target with pointer-sized atomics");
 
 /// Miscellaneous things used by `FoldChain` and `FoldList`
 pub mod misc;
@@ -422,4 +436,47 @@ pub mod fold_simplification;
 pub mod fold_chain;
 
 ///`FoldList`s and slices thereof; See [FoldList](crate).
-pub mod fold_list;
\ No newline at end of file
+pub mod fold_list;
+
+///Spare node allocations which a `FoldChain` or `FoldList` can reuse; see [`NodePool`](node_pool::NodePool).
+pub mod node_pool;
+
+///A `FoldList` which stores its elements in inline chunks, for less memory per element; see [`ChunkedFoldList`](chunked::ChunkedFoldList).
+pub mod chunked;
+
+///A text buffer built on a `FoldList` of string chunks; see [`Rope`](rope::Rope).
+pub mod rope;
+
+///A `FoldList` which keeps its elements sorted by key; see [`SortedFoldList`](sorted_fold_list::SortedFoldList).
+pub mod sorted_fold_list;
+
+///An ordered map whose values can be folded over any range of keys; see [`FoldMap`](fold_map::FoldMap).
+pub mod fold_map;
+
+///A min-priority queue whose elements' priorities can be changed, and which can be melded with another; see [`FoldPriorityQueue`](priority_queue::FoldPriorityQueue). Like handles, this needs the `handles` feature.
+#[cfg(feature = "handles")]
+pub mod priority_queue;
+
+///A queue for sliding-window folds, with amortized *O*(1) pushes and pops; see [`FoldWindow`](fold_window::FoldWindow).
+pub mod fold_window;
+
+///Cursors which walk a `FoldList` element by element; see [`Cursor`](cursor::Cursor) and [`CursorMut`](cursor::CursorMut).
+pub mod cursor;
+
+///Handles which keep referring to an element of a `FoldList` as it moves around; see [`Handle`](handle::Handle). These need the `handles` feature.
+#[cfg(feature = "handles")]
+pub mod handle;
+
+///Ready-made zero-sized [`FoldSettings`](fold_settings::FoldSettings) types for common folds, such as sums and minimums.
+pub mod monoids;
+
+///Checkers for the laws that [`FoldSettings`](fold_settings::FoldSettings) and [`FoldSimplification`](fold_simplification::FoldSimplification) implementations must satisfy.
+pub mod laws;
+
+///Serialization of `FoldList`s and `FoldChain`s as sequences of elements, and deserialization into given settings via [`FoldListSeed`](serde::FoldListSeed) and [`FoldChainSeed`](serde::FoldChainSeed). Requires the `serde` feature.
+#[cfg(feature = "serde")]
+pub mod serde;
+
+///Parallel iteration over `FoldList`s and their views, and parallel construction and mutation of `FoldList`s and `FoldChain`s. Requires the `rayon` feature.
+#[cfg(feature = "rayon")]
+pub mod rayon;
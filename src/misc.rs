@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use core::marker::PhantomData;
 
 /// A custom version of `Fn(A)->B`. Every `Fn(A)->B` is also a `Fun<A,B>`.
 pub trait Fun<A,B> {
@@ -98,6 +98,23 @@ impl<T> SingleEndedRange<T> for core::ops::RangeTo<T> {
     }
 }
 
+/// The error returned by the `try_` versions of indexing operations, such as [`try_insert_at`](crate::fold_list::MutFoldListSlice::try_insert_at), when the index is out of bounds.
+#[derive(Clone,Copy,Debug,PartialEq,Eq,Hash)]
+pub struct IndexOutOfBounds {
+    /// The index which was out of bounds.
+    pub index: usize,
+    /// The length of the list or slice at the time.
+    pub len: usize,
+}
+
+impl core::fmt::Display for IndexOutOfBounds {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Index out of bounds: the index is {} but the length is {}", self.index, self.len)
+    }
+}
+
+impl core::error::Error for IndexOutOfBounds {}
+
 /// A trait for type-level booleans, used internally to implement slice types more efficiently.
 /// 
 /// This trait is sealed and cannot be implemented for types outside this crate.
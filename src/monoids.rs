@@ -0,0 +1,299 @@
+use core::ops::{Add, BitXor, Mul, Rem, Sub};
+
+use crate::fold_settings::{FoldSettings, InvertibleFoldSettings};
+
+/// A numeric type with an additive identity, used by [`Sum`], [`Xor`] and [`Gcd`].
+pub trait Zero {
+    /// The additive identity, `0`.
+    const ZERO: Self;
+}
+
+/// A numeric type with a multiplicative identity, used by [`Product`].
+pub trait One {
+    /// The multiplicative identity, `1`.
+    const ONE: Self;
+}
+
+macro_rules! impl_zero_one {
+    ($zero:expr, $one:expr; $($t:ty),*) => {
+        $(
+            impl Zero for $t {
+                const ZERO: Self = $zero;
+            }
+            impl One for $t {
+                const ONE: Self = $one;
+            }
+        )*
+    };
+}
+
+impl_zero_one!(0, 1; u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+impl_zero_one!(0.0, 1.0; f32, f64);
+
+/// Folds elements by adding them, starting from [`Zero::ZERO`].
+///
+/// For types with subtraction, it is also an [`InvertibleFoldSettings`], although for floats, range folds computed by subtracting prefix folds may be rounded differently.
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq,Hash)]
+pub struct Sum;
+impl<T: Clone + Add<Output = T> + Zero> FoldSettings<T,T> for Sum {
+    fn op(&self, a: T, b: T) -> T {
+        a + b
+    }
+    fn delta_of(&self, t: &T) -> T {
+        t.clone()
+    }
+    fn empty(&self) -> T {
+        T::ZERO
+    }
+}
+impl<T: Clone + Add<Output = T> + Sub<Output = T> + Zero> InvertibleFoldSettings<T,T> for Sum {
+    fn subtract(&self, a: T, b: T) -> T {
+        a - b
+    }
+}
+
+/// Folds elements by multiplying them, starting from [`One::ONE`].
+///
+/// The multiplication is done from left to right, so this also works for non-commutative types.
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq,Hash)]
+pub struct Product;
+impl<T: Clone + Mul<Output = T> + One> FoldSettings<T,T> for Product {
+    fn op(&self, a: T, b: T) -> T {
+        a * b
+    }
+    fn delta_of(&self, t: &T) -> T {
+        t.clone()
+    }
+    fn empty(&self) -> T {
+        T::ONE
+    }
+}
+
+/// Folds elements into their minimum, which is [`None`] for an empty range. Ties are broken in favor of the leftmost element.
+///
+/// This only needs [`PartialOrd`], so it works for floats, but the fold is only well-defined if the elements are all comparable with each other (e.g. no `NaN`s).
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq,Hash)]
+pub struct Min;
+impl<T: Clone + PartialOrd> FoldSettings<T,Option<T>> for Min {
+    fn op(&self, a: Option<T>, b: Option<T>) -> Option<T> {
+        match (a,b) {
+            (Some(a), Some(b)) => Some(if b < a {b} else {a}),
+            (a, b) => a.or(b),
+        }
+    }
+    fn delta_of(&self, t: &T) -> Option<T> {
+        Some(t.clone())
+    }
+    fn empty(&self) -> Option<T> {
+        None
+    }
+}
+
+/// Folds elements into their maximum, which is [`None`] for an empty range. Ties are broken in favor of the leftmost element.
+///
+/// Like [`Min`], this only needs [`PartialOrd`], but the elements must all be comparable with each other.
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq,Hash)]
+pub struct Max;
+impl<T: Clone + PartialOrd> FoldSettings<T,Option<T>> for Max {
+    fn op(&self, a: Option<T>, b: Option<T>) -> Option<T> {
+        match (a,b) {
+            (Some(a), Some(b)) => Some(if b > a {b} else {a}),
+            (a, b) => a.or(b),
+        }
+    }
+    fn delta_of(&self, t: &T) -> Option<T> {
+        Some(t.clone())
+    }
+    fn empty(&self) -> Option<T> {
+        None
+    }
+}
+
+/// Folds elements into their minimum and maximum, as `Some((min,max))`, which is [`None`] for an empty range.
+///
+/// Like [`Min`], this only needs [`PartialOrd`], but the elements must all be comparable with each other.
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq,Hash)]
+pub struct MinMax;
+impl<T: Clone + PartialOrd> FoldSettings<T,Option<(T,T)>> for MinMax {
+    fn op(&self, a: Option<(T,T)>, b: Option<(T,T)>) -> Option<(T,T)> {
+        match (a,b) {
+            (Some((min_a,max_a)), Some((min_b,max_b))) => Some((
+                if min_b < min_a {min_b} else {min_a},
+                if max_b > max_a {max_b} else {max_a},
+            )),
+            (a, b) => a.or(b),
+        }
+    }
+    fn delta_of(&self, t: &T) -> Option<(T,T)> {
+        Some((t.clone(),t.clone()))
+    }
+    fn empty(&self) -> Option<(T,T)> {
+        None
+    }
+}
+
+/// Folds elements into their minimum along with its position, as `(len, Some((min,position)))`, where `len` is the number of elements in the range.
+///
+/// The position is counted from the left of the folded range, and ties are broken in favor of the leftmost element.
+/// The minimum is [`None`] for an empty range. Like [`Min`], this only needs [`PartialOrd`], but the elements must all be comparable with each other.
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq,Hash)]
+pub struct ArgMin;
+impl<T: Clone + PartialOrd> FoldSettings<T,(usize,Option<(T,usize)>)> for ArgMin {
+    fn op(&self, (n,a): (usize,Option<(T,usize)>), (m,b): (usize,Option<(T,usize)>)) -> (usize,Option<(T,usize)>) {
+        let b = b.map(|(b,i)| (b,i+n));
+        (n + m, match (a,b) {
+            (Some(a), Some(b)) => Some(if b.0 < a.0 {b} else {a}),
+            (a, b) => a.or(b),
+        })
+    }
+    fn delta_of(&self, t: &T) -> (usize,Option<(T,usize)>) {
+        (1,Some((t.clone(),0)))
+    }
+    fn empty(&self) -> (usize,Option<(T,usize)>) {
+        (0,None)
+    }
+}
+
+/// Folds elements into their maximum along with its position, as `(len, Some((max,position)))`, where `len` is the number of elements in the range.
+///
+/// The position is counted from the left of the folded range, and ties are broken in favor of the leftmost element.
+/// The maximum is [`None`] for an empty range. Like [`Min`], this only needs [`PartialOrd`], but the elements must all be comparable with each other.
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq,Hash)]
+pub struct ArgMax;
+impl<T: Clone + PartialOrd> FoldSettings<T,(usize,Option<(T,usize)>)> for ArgMax {
+    fn op(&self, (n,a): (usize,Option<(T,usize)>), (m,b): (usize,Option<(T,usize)>)) -> (usize,Option<(T,usize)>) {
+        let b = b.map(|(b,i)| (b,i+n));
+        (n + m, match (a,b) {
+            (Some(a), Some(b)) => Some(if b.0 > a.0 {b} else {a}),
+            (a, b) => a.or(b),
+        })
+    }
+    fn delta_of(&self, t: &T) -> (usize,Option<(T,usize)>) {
+        (1,Some((t.clone(),0)))
+    }
+    fn empty(&self) -> (usize,Option<(T,usize)>) {
+        (0,None)
+    }
+}
+
+/// Folds elements into their number. This is mostly useful for a [`FoldChain`](crate::fold_chain::FoldChain), since a `FoldList` already keeps track of its size.
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq,Hash)]
+pub struct Count;
+impl<T> FoldSettings<T,usize> for Count {
+    fn op(&self, a: usize, b: usize) -> usize {
+        a + b
+    }
+    fn delta_of(&self, _: &T) -> usize {
+        1
+    }
+    fn empty(&self) -> usize {
+        0
+    }
+}
+impl<T> InvertibleFoldSettings<T,usize> for Count {
+    fn subtract(&self, a: usize, b: usize) -> usize {
+        a - b
+    }
+}
+
+/// Folds elements by xor-ing them, starting from [`Zero::ZERO`].
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq,Hash)]
+pub struct Xor;
+impl<T: Clone + BitXor<Output = T> + Zero> FoldSettings<T,T> for Xor {
+    fn op(&self, a: T, b: T) -> T {
+        a ^ b
+    }
+    fn delta_of(&self, t: &T) -> T {
+        t.clone()
+    }
+    fn empty(&self) -> T {
+        T::ZERO
+    }
+}
+impl<T: Clone + BitXor<Output = T> + Zero> InvertibleFoldSettings<T,T> for Xor {
+    fn subtract(&self, a: T, b: T) -> T {
+        a ^ b
+    }
+}
+
+/// Folds elements into their greatest common divisor, via the Euclidean algorithm. The empty range's fold is [`Zero::ZERO`], which is a divisor of everything.
+///
+/// This is meant for unsigned integers; for signed integers, the sign of the result isn't specified.
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq,Hash)]
+pub struct Gcd;
+impl<T: Clone + Rem<Output = T> + PartialEq + Zero> FoldSettings<T,T> for Gcd {
+    fn op(&self, mut a: T, mut b: T) -> T {
+        while b != T::ZERO {
+            let r = a % b.clone();
+            a = b;
+            b = r;
+        }
+        a
+    }
+    fn delta_of(&self, t: &T) -> T {
+        t.clone()
+    }
+    fn empty(&self) -> T {
+        T::ZERO
+    }
+}
+
+/// Folds `bool` elements into whether any of them are `true`.
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq,Hash)]
+pub struct Any;
+impl FoldSettings<bool,bool> for Any {
+    fn op(&self, a: bool, b: bool) -> bool {
+        a || b
+    }
+    fn delta_of(&self, t: &bool) -> bool {
+        *t
+    }
+    fn empty(&self) -> bool {
+        false
+    }
+}
+
+/// Folds `bool` elements into whether all of them are `true`.
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq,Hash)]
+pub struct All;
+impl FoldSettings<bool,bool> for All {
+    fn op(&self, a: bool, b: bool) -> bool {
+        a && b
+    }
+    fn delta_of(&self, t: &bool) -> bool {
+        *t
+    }
+    fn empty(&self) -> bool {
+        true
+    }
+}
+
+/// Folds elements into the leftmost one, which is [`None`] for an empty range.
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq,Hash)]
+pub struct First;
+impl<T: Clone> FoldSettings<T,Option<T>> for First {
+    fn op(&self, a: Option<T>, b: Option<T>) -> Option<T> {
+        a.or(b)
+    }
+    fn delta_of(&self, t: &T) -> Option<T> {
+        Some(t.clone())
+    }
+    fn empty(&self) -> Option<T> {
+        None
+    }
+}
+
+/// Folds elements into the rightmost one, which is [`None`] for an empty range.
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq,Hash)]
+pub struct Last;
+impl<T: Clone> FoldSettings<T,Option<T>> for Last {
+    fn op(&self, a: Option<T>, b: Option<T>) -> Option<T> {
+        b.or(a)
+    }
+    fn delta_of(&self, t: &T) -> Option<T> {
+        Some(t.clone())
+    }
+    fn empty(&self) -> Option<T> {
+        None
+    }
+}
@@ -0,0 +1,104 @@
+use core::mem::MaybeUninit;
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::fold_chain::WAVLNode;
+
+/// Spare allocations for the nodes of a [`FoldChain`](crate::fold_chain::FoldChain) or [`FoldList`](crate::fold_list::FoldList), each of which holds one element.
+///
+/// Every element gets its own allocation, which is normally freed when the element is removed.
+/// A chain or list made with a pool (via [`FoldChain::from_settings_and_pool`](crate::fold_chain::FoldChain::from_settings_and_pool) or [`FoldList::from_settings_and_pool`](crate::fold_list::FoldList::from_settings_and_pool))
+/// instead keeps the allocations of removed elements in its pool, and takes new ones from the pool before allocating.
+///
+/// To reuse allocations between lists, turn a list which isn't needed anymore into its pool with [`into_node_pool`](crate::fold_chain::FoldChain::into_node_pool), and make the next list with it.
+/// The pool only recycles these per-element allocations; it isn't an arena, so each spare allocation is still made and freed separately with the global allocator.
+///
+/// A chain made with [`from_settings`](crate::fold_chain::FoldChain::from_settings) still takes allocations from its pool if some were made with [`ensure_capacity`](NodePool::ensure_capacity), but doesn't keep the allocations of removed elements.
+///
+/// Chains made from a pooled chain, by [`take_all`](crate::fold_chain::MutFoldChainSlice::take_all), [`split_off`](crate::fold_list::FoldList::split_off) or [`clone`](Clone::clone),
+/// start with an empty pool which also keeps the allocations of removed elements; the spare allocations stay with the original chain.
+pub struct NodePool<T,D> {
+    spare: Vec<Box<MaybeUninit<WAVLNode<T,D>>>>,
+    //whether the allocations of removed nodes are kept, rather than freed
+    keeps_freed: bool,
+}
+
+//the spare allocations never hold an element, so these only need the bounds a chain of them would need
+unsafe impl<T: Sync, D: Sync> Sync for NodePool<T, D> {}
+unsafe impl<T: Send, D: Send> Send for NodePool<T, D> {}
+
+impl<T, D> NodePool<T, D> {
+    /// Create a new empty pool.
+    pub fn new() -> Self {
+        Self { spare: Vec::new(), keeps_freed: true }
+    }
+
+    /// Create a new pool with `capacity` spare allocations.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut ret = Self::new();
+        ret.ensure_capacity(capacity);
+        ret
+    }
+
+    //the pool of a chain which doesn't keep the allocations of removed nodes
+    pub(crate) fn non_keeping() -> Self {
+        Self { spare: Vec::new(), keeps_freed: false }
+    }
+
+    //an empty pool which keeps freed allocations iff this one does, for chains split off from this one's
+    pub(crate) fn empty_like(&self) -> Self {
+        Self { spare: Vec::new(), keeps_freed: self.keeps_freed }
+    }
+
+    /// Get the number of spare allocations, i.e. the number of elements which can be added without allocating.
+    pub fn capacity(&self) -> usize {
+        self.spare.len()
+    }
+
+    /// Allocate spare nodes until there are at least `capacity` of them in total. Unlike [`Vec::reserve`], this doesn't add to the spare nodes which are already there.
+    pub fn ensure_capacity(&mut self, capacity: usize) {
+        let missing = capacity.saturating_sub(self.spare.len());
+        self.spare.reserve(missing);
+        self.spare.extend((0..missing).map(|_| Box::new_uninit()));
+    }
+
+    /// Free every spare allocation.
+    pub fn shrink_to_fit(&mut self) {
+        self.spare = Vec::new();
+    }
+
+    pub(crate) fn alloc(&mut self, node: WAVLNode<T,D>) -> Box<WAVLNode<T,D>> {
+        match self.spare.pop() {
+            Some(slot) => unsafe {
+                let ptr = Box::into_raw(slot).cast::<WAVLNode<T,D>>();
+                ptr.write(node);
+                Box::from_raw(ptr)
+            },
+            None => Box::new(node),
+        }
+    }
+
+    //moves the node out of its allocation, which is kept if this pool keeps freed allocations
+    pub(crate) fn free(&mut self, node: Box<WAVLNode<T,D>>) -> WAVLNode<T,D> {
+        if !self.keeps_freed {
+            return *node
+        }
+        unsafe {
+            let ptr = Box::into_raw(node);
+            let ret = ptr.read();
+            self.spare.push(Box::from_raw(ptr.cast::<MaybeUninit<WAVLNode<T,D>>>()));
+            ret
+        }
+    }
+
+    //makes this pool keep the allocations of removed nodes from now on
+    pub(crate) fn into_keeping(mut self) -> Self {
+        self.keeps_freed = true;
+        self
+    }
+}
+
+impl<T, D> Default for NodePool<T, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{fold_chain::Iter, fold_list::{FoldList, MutFoldListSlice}, fold_settings::FoldSettings, handle::Handle, misc::False, monoids::Min};
+
+/// The [`FoldSettings`] of a [`FoldPriorityQueue`], which folds `(P,T)` elements into the smallest priority, `P`, or [`None`] if there are no elements.
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq,Hash)]
+pub struct PrioritySettings;
+impl<P: Clone + Ord, T> FoldSettings<(P,T),Option<P>> for PrioritySettings {
+    fn op(&self, a: Option<P>, b: Option<P>) -> Option<P> {
+        Min.op(a, b)
+    }
+    fn delta_of(&self, (p,_): &(P,T)) -> Option<P> {
+        Some(p.clone())
+    }
+    fn empty(&self) -> Option<P> {
+        None
+    }
+}
+
+/// A handle to an element of a [`FoldPriorityQueue`], as returned by [`FoldPriorityQueue::push`]. See [`Handle`].
+pub type PriorityHandle<P,T> = Handle<(P,T),Option<P>>;
+
+/// A min-priority queue of elements of type `T` with priorities of type `P`, stored as a [`FoldList`] of `(P,T)` pairs in the order they were pushed.
+///
+/// [`push`](FoldPriorityQueue::push), [`peek_min`](FoldPriorityQueue::peek_min), [`pop_min`](FoldPriorityQueue::pop_min), [`change_priority`](FoldPriorityQueue::change_priority),
+/// [`remove`](FoldPriorityQueue::remove) and [`meld`](FoldPriorityQueue::meld) are all *O*(log(n)).
+///
+/// Of the elements with the smallest priority, the one that was pushed first is the minimum, so elements with equal priorities come out in FIFO order.
+/// For a max-priority queue, use [`Reverse`](core::cmp::Reverse) priorities.
+///
+/// The handles returned by [`push`](FoldPriorityQueue::push) aren't valid for clones of this queue.
+pub struct FoldPriorityQueue<P: Clone + Ord, T> {
+    list: FoldList<(P,T), Option<P>, PrioritySettings>,
+}
+
+impl<P: Clone + Ord, T> FoldPriorityQueue<P, T> {
+    /// Create a new empty `FoldPriorityQueue`.
+    pub fn new() -> Self {
+        Self { list: FoldList::from_settings(PrioritySettings) }
+    }
+
+    /// Get the `FoldList` which underlies this queue, whose elements are in the order they were pushed.
+    pub fn as_fold_list(&self) -> &FoldList<(P,T), Option<P>, PrioritySettings> {
+        &self.list
+    }
+
+    /// Get the number of elements in this queue.
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Returns `true` if this queue has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Remove every element from this queue.
+    pub fn clear(&mut self) {
+        self.list.take_all();
+    }
+
+    /// Add `value` with priority `priority`, and return a [`Handle`] to it, which can be used to change its priority or remove it later.
+    pub fn push(&mut self, priority: P, value: T) -> PriorityHandle<P,T> {
+        self.list.append_right_with_handle((priority, value))
+    }
+
+    /// Get the smallest priority in this queue, or [`None`] if it is empty. This is *O*(1).
+    pub fn min_priority(&self) -> Option<P> {
+        self.list.fold()
+    }
+
+    /// Get the element with the smallest priority, or [`None`] if this queue is empty.
+    pub fn peek_min(&self) -> Option<(&P, &T)> {
+        let min = self.min_priority()?;
+        self.list
+            .find_left(|d| d.as_ref().is_some_and(|p| *p <= min))
+            .map(|(_,(p,t),_)| (p,t))
+    }
+
+    /// Remove and return the element with the smallest priority, or return [`None`] if this queue is empty.
+    pub fn pop_min(&mut self) -> Option<(P, T)> {
+        let min = self.min_priority()?;
+        self.list
+            .mut_view_drop_left_until(|d| d.as_ref().is_some_and(|p| *p <= min))
+            .pop_left()
+    }
+
+    /// Get the priority and value of the element of `handle`, or [`None`] if it isn't in this queue.
+    pub fn get(&self, handle: &PriorityHandle<P,T>) -> Option<(&P, &T)> {
+        self.list.value_of(handle).map(|(p,t)| (p,t))
+    }
+
+    /// Set the priority of the element of `handle` to `priority`, and return its old priority, or return [`None`] if it isn't in this queue.
+    ///
+    /// The element keeps its place among elements with equal priorities, as if it had been pushed with its new priority in the first place.
+    pub fn change_priority(&mut self, handle: &PriorityHandle<P,T>, priority: P) -> Option<P> {
+        self.list.update_by_handle(handle, |(p,_)| core::mem::replace(p, priority))
+    }
+
+    /// Mutate the value (but not the priority) of the element of `handle` via a closure, and return the result of the closure, or return [`None`] if it isn't in this queue.
+    pub fn update_value<R>(&mut self, handle: &PriorityHandle<P,T>, f: impl FnOnce(&mut T)->R) -> Option<R> {
+        self.list.update_by_handle(handle, |(_,t)| f(t))
+    }
+
+    /// Remove and return the element of `handle`, or return [`None`] if it isn't in this queue.
+    pub fn remove(&mut self, handle: &PriorityHandle<P,T>) -> Option<(P, T)> {
+        self.list.remove_by_handle(handle)
+    }
+
+    /// Move every element of `other` into this queue. This is *O*(log(n)).
+    ///
+    /// The elements of `other` count as having been pushed after those of this queue, and the handles to them are now valid for this queue instead.
+    pub fn meld(&mut self, other: FoldPriorityQueue<P, T>) {
+        self.list.append_all_right(other.list);
+    }
+
+    /// Iterate over the elements of this queue, in the order they were pushed.
+    pub fn iter(&self) -> Iter<'_, False, (P,T), (usize,Option<P>)> {
+        self.list.iter()
+    }
+}
+
+impl<P: Clone + Ord, T: Clone> Clone for FoldPriorityQueue<P, T> {
+    fn clone(&self) -> Self {
+        Self { list: self.list.clone() }
+    }
+}
+
+impl<P: Clone + Ord + core::fmt::Debug, T: core::fmt::Debug> core::fmt::Debug for FoldPriorityQueue<P, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.list.fmt(f)
+    }
+}
+
+impl<P: Clone + Ord, T> Default for FoldPriorityQueue<P, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P: Clone + Ord, T> FromIterator<(P,T)> for FoldPriorityQueue<P, T> {
+    fn from_iter<I: IntoIterator<Item = (P,T)>>(iter: I) -> Self {
+        Self { list: iter.into_iter().collect() }
+    }
+}
+
+impl<P: Clone + Ord, T> Extend<(P,T)> for FoldPriorityQueue<P, T> {
+    fn extend<I: IntoIterator<Item = (P,T)>>(&mut self, iter: I) {
+        self.list.append_right_from_iter(iter.into_iter());
+    }
+}
+
+impl<'a, P: Clone + Ord, T> IntoIterator for &'a FoldPriorityQueue<P, T> {
+    type Item = &'a (P,T);
+    type IntoIter = Iter<'a, False, (P,T), (usize,Option<P>)>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,176 @@
+use core::{marker::PhantomData, ops::Range, ptr::NonNull};
+use alloc::vec::Vec;
+
+use ::rayon::iter::{plumbing::{bridge, Consumer, Producer, ProducerCallback, UnindexedConsumer}, IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+
+use crate::{fold_chain::{FoldChain, ImmFoldChainSliceStruct, Iter, WAVLNode}, fold_list::{FoldList, FoldListSlice, FoldListSliceFrom}, fold_settings::{FoldSettings, SettingsWithSize}, fold_simplification::FoldSimplification, misc::{Bool, False}};
+
+/// A parallel iterator that emits immutable references to the elements of a [`FoldList`] or a view thereof.
+///
+/// It's split by index, each split costing *O*(log(n)), so it's an [`IndexedParallelIterator`].
+/// Create one with [`par_iter`](::rayon::iter::IntoParallelRefIterator::par_iter) on a `FoldList`, or [`into_par_iter`](IntoParallelIterator::into_par_iter) on an immutable view.
+pub struct ParIter<'a,IsReversed: Bool,T,D: Clone> {
+    root: Option<NonNull<WAVLNode<T,(usize,D)>>>,
+    //indices into the whole tree, from left to right regardless of reversal
+    range: Range<usize>,
+    _m: PhantomData<(IsReversed,&'a T)>
+}
+
+//send and sync are justified because this only hands out shared references into the tree, which is borrowed for 'a
+unsafe impl<'a, IsReversed: Bool, T: Sync, D: Clone + Sync> Send for ParIter<'a, IsReversed, T, D> {}
+unsafe impl<'a, IsReversed: Bool, T: Sync, D: Clone + Sync> Sync for ParIter<'a, IsReversed, T, D> {}
+
+impl<'a, IsReversed: Bool, T, D: Clone> ParIter<'a, IsReversed, T, D> {
+    fn new<Settings: FoldSettings<T,(usize,D)>, Simplification: FoldSimplification<T,(usize,D)>, IsFlushLeft: Bool, IsFlushRight: Bool>(slice: ImmFoldChainSliceStruct<'a, IsReversed, IsFlushLeft, IsFlushRight, Settings, Simplification, T, (usize,D)>) -> Self {
+        let (root, range) = match slice.rayon_range() {
+            Some((root, range)) => (Some(root), range),
+            None => (None, 0..0),
+        };
+        Self { root, range, _m: PhantomData }
+    }
+}
+
+impl<'a, IsReversed: Bool, T: Sync + 'a, D: Clone + Sync + 'a> ParallelIterator for ParIter<'a, IsReversed, T, D> {
+    type Item = &'a T;
+
+    fn drive_unindexed<C: UnindexedConsumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.range.len())
+    }
+}
+
+impl<'a, IsReversed: Bool, T: Sync + 'a, D: Clone + Sync + 'a> IndexedParallelIterator for ParIter<'a, IsReversed, T, D> {
+    fn len(&self) -> usize {
+        self.range.len()
+    }
+
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        callback.callback(self)
+    }
+}
+
+impl<'a, IsReversed: Bool, T: Sync + 'a, D: Clone + Sync + 'a> Producer for ParIter<'a, IsReversed, T, D> {
+    type Item = &'a T;
+    type IntoIter = ProducerIter<'a, IsReversed, T, D>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let len = self.range.len();
+        ProducerIter { iter: unsafe { Iter::rayon_from_range(self.root, self.range) }, len }
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let Range { start, end } = self.range;
+        let (left, right) = if IsReversed::b {
+            (end - index..end, start..end - index)
+        } else {
+            (start..start + index, start + index..end)
+        };
+        (Self { root: self.root, range: left, _m: PhantomData }, Self { root: self.root, range: right, _m: PhantomData })
+    }
+}
+
+/// The sequential iterator which each piece of a [`ParIter`] is turned into.
+pub struct ProducerIter<'a,IsReversed: Bool,T,D: Clone> {
+    iter: Iter<'a,IsReversed,T,(usize,D)>,
+    len: usize,
+}
+
+impl<'a, IsReversed: Bool, T, D: Clone> Iterator for ProducerIter<'a, IsReversed, T, D> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let ret = self.iter.next()?;
+        self.len -= 1;
+        Some(ret)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, IsReversed: Bool, T, D: Clone> DoubleEndedIterator for ProducerIter<'a, IsReversed, T, D> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let ret = self.iter.next_back()?;
+        self.len -= 1;
+        Some(ret)
+    }
+}
+
+impl<'a, IsReversed: Bool, T, D: Clone> ExactSizeIterator for ProducerIter<'a, IsReversed, T, D> {}
+
+impl<'a, T: Sync + 'a, D: Clone + Sync + 'a, Settings: FoldSettings<T,D>> IntoParallelIterator for &'a FoldList<T, D, Settings> {
+    type Iter = ParIter<'a, False, T, D>;
+    type Item = &'a T;
+    fn into_par_iter(self) -> Self::Iter {
+        ParIter::new(self.as_imm().as_sized_chain())
+    }
+}
+
+impl<'a, IsReversed: Bool, IsFlushLeft: Bool, IsFlushRight: Bool, T: Sync + 'a, D: Clone + Sync + 'a, Settings: FoldSettings<T,D> + 'a, Simplification: FoldSimplification<T,D> + 'a>
+IntoParallelIterator for FoldListSliceFrom<'a, T, D, Settings, Simplification, ImmFoldChainSliceStruct<'a, IsReversed, IsFlushLeft, IsFlushRight, SettingsWithSize<Settings>, (), T, (usize,D)>> {
+    type Iter = ParIter<'a, IsReversed, T, D>;
+    type Item = &'a T;
+    fn into_par_iter(self) -> Self::Iter {
+        ParIter::new(self.as_sized_chain())
+    }
+}
+
+//the number of elements below which par_from_vec builds sequentially
+const MIN_PAR_BUILD_LEN: usize = 1 << 12;
+
+//builds subtrees of halves of the vec in parallel, then joins them with append_all_right
+fn par_build_chain<T: Send, D: Clone + Send, Settings: FoldSettings<T,D> + Send + Sync>(mut vec: Vec<T>, settings: Settings, min_len: usize) -> FoldChain<T, D, Settings> {
+    if vec.len() <= min_len {
+        let mut ret = FoldChain::from_settings(settings);
+        ret.append_right_from_iter(vec.into_iter());
+        return ret
+    }
+    let right = vec.split_off(vec.len() / 2);
+    let (mut left, right) = ::rayon::join(
+        || par_build_chain(vec, settings, min_len),
+        || par_build_chain(right, settings, min_len),
+    );
+    left.append_all_right(right);
+    left
+}
+
+impl<T: Send, D: Clone + Send, Settings: FoldSettings<T,D> + Send + Sync> FoldChain<T, D, Settings> {
+    /// Create a new `FoldChain` with the specified [Settings](crate#fold-settings), containing the elements of `vec` from left to right.
+    ///
+    /// The vec is split into pieces whose subtrees (and their folds) are built in parallel, and then joined together in *O*(log(n)) each. Requires the `rayon` feature.
+    pub fn par_from_vec(settings: Settings, vec: Vec<T>) -> Self {
+        let min_len = (vec.len() / (::rayon::current_num_threads() * 4)).max(MIN_PAR_BUILD_LEN);
+        par_build_chain(vec, settings, min_len)
+    }
+
+    /// Mutate every element via `f`, in parallel, then recalculate the folds.
+    ///
+    /// The tree is split at its root, and the two halves are handled in parallel, recursively. The folds of each subtree are recalculated once it's done,
+    /// so this is *O*(n) work in total. Requires the `rayon` feature.
+    pub fn par_foreach_mut(&mut self, f: impl Fn(&mut T) + Sync) {
+        self.rayon_for_each_mut(&f);
+    }
+}
+
+impl<T: Send, D: Clone + Send, Settings: FoldSettings<T,D> + Send + Sync> FoldList<T, D, Settings> {
+    /// Create a new `FoldList` with the specified [Settings](crate#fold-settings), containing the elements of `vec` from left to right.
+    ///
+    /// See [`FoldChain::par_from_vec`]. Requires the `rayon` feature.
+    pub fn par_from_vec(settings: Settings, vec: Vec<T>) -> Self {
+        Self { underlying: FoldChain::par_from_vec(SettingsWithSize(settings), vec) }
+    }
+
+    /// Mutate every element via `f`, in parallel, then recalculate the folds.
+    ///
+    /// See [`FoldChain::par_foreach_mut`]. Requires the `rayon` feature.
+    pub fn par_foreach_mut(&mut self, f: impl Fn(&mut T) + Sync) {
+        self.underlying.par_foreach_mut(f);
+    }
+}
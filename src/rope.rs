@@ -0,0 +1,482 @@
+use core::{fmt, ops::RangeBounds};
+use alloc::string::String;
+
+use crate::{fold_chain::Iter, fold_list::{range_to_start_end, FoldList, FoldListSlice, MutFoldListSlice}, fold_settings::{CachedDelta, CachedDeltaSettings, FoldSettings}, misc::False};
+
+//chunks are split so that they're never longer than this, and merged with or rebalanced against a neighbor when they're less than half of it,
+//so every chunk but a lone one is at least half full, give or take the bytes of one char
+const MAX_CHUNK_LEN: usize = 512;
+
+/// The fold of a piece of text, as used by [`Rope`]: how many bytes, chars and newlines (`'\n'`) it has.
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq,Hash)]
+pub struct TextSummary {
+    /// The number of bytes, i.e. the length of the text in UTF-8.
+    pub bytes: usize,
+    /// The number of [`char`]s.
+    pub chars: usize,
+    /// The number of `'\n'`s.
+    pub newlines: usize,
+}
+
+impl TextSummary {
+    /// Get the summary of `text`. This is *O*(`text.len()`).
+    pub fn of(text: &str) -> Self {
+        Self {
+            bytes: text.len(),
+            chars: text.chars().count(),
+            newlines: text.bytes().filter(|&b| b == b'\n').count(),
+        }
+    }
+}
+
+/// The [`FoldSettings`] of the chunks of a [`Rope`], which fold `String`s into their [`TextSummary`], by adding the counts.
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq,Hash)]
+pub struct TextMetrics;
+impl FoldSettings<String,TextSummary> for TextMetrics {
+    fn op(&self, a: TextSummary, b: TextSummary) -> TextSummary {
+        TextSummary {
+            bytes: a.bytes + b.bytes,
+            chars: a.chars + b.chars,
+            newlines: a.newlines + b.newlines,
+        }
+    }
+    fn delta_of(&self, t: &String) -> TextSummary {
+        TextSummary::of(t)
+    }
+    fn empty(&self) -> TextSummary {
+        TextSummary::default()
+    }
+}
+
+type Chunk = CachedDelta<String,TextSummary>;
+
+/// The `FoldList` of chunks which underlies a [`Rope`].
+pub type RopeChunkList = FoldList<Chunk, TextSummary, CachedDeltaSettings<TextMetrics>>;
+
+/// A text buffer, stored as a [`FoldList`] of string chunks of up to a few hundred bytes, each with its cached [`TextSummary`].
+///
+/// Text can be inserted and removed anywhere in *O*(log(n)), and positions can be converted between byte offsets, char offsets and lines and columns in *O*(log(n)),
+/// where n is the length of the text. (Strictly, each operation also scans the chunk where it happens, which takes time proportional to the maximum chunk length.)
+///
+/// Positions are byte offsets unless stated otherwise. Byte offsets which aren't on a [char boundary](str::is_char_boundary) cause a panic, as they would for `str`.
+/// Lines are separated by `'\n'`, so a rope with `k` newlines has `k + 1` lines, the last of which may be empty.
+#[derive(Clone,Default)]
+pub struct Rope {
+    chunks: RopeChunkList,
+}
+
+impl Rope {
+    /// Create a new empty `Rope`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the `FoldList` of chunks which underlies this rope, e.g. to perform searches on its [`TextSummary`]s.
+    pub fn as_fold_list(&self) -> &RopeChunkList {
+        &self.chunks
+    }
+
+    fn summary(&self) -> TextSummary {
+        self.chunks.fold()
+    }
+
+    /// Get the length of this rope in bytes. This is *O*(1).
+    pub fn len_bytes(&self) -> usize {
+        self.summary().bytes
+    }
+
+    /// Get the number of [`char`]s in this rope. This is *O*(1).
+    pub fn len_chars(&self) -> usize {
+        self.summary().chars
+    }
+
+    /// Get the number of lines in this rope, which is one more than the number of newlines. This is *O*(1).
+    pub fn len_lines(&self) -> usize {
+        self.summary().newlines + 1
+    }
+
+    /// Returns `true` if this rope has no text.
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    //finds the chunk holding the byte at `byte`, along with `byte`'s offset in it and the summary of every chunk before it
+    fn locate_byte(&self, byte: usize) -> Option<(usize, usize, &str, TextSummary)> {
+        let (index, chunk, before) = self.chunks.find_left(|s| s.bytes > byte)?;
+        Some((index, byte - before.bytes, chunk.value(), before))
+    }
+
+    //like locate_byte, but also allows `byte == self.len_bytes()`, which is located at the end of the last chunk.
+    //panics if `byte` is out of bounds or not on a char boundary
+    fn locate_position(&self, byte: usize) -> (usize, usize, &str, TextSummary) {
+        let located = self.locate_byte(byte).or_else(|| {
+            let (last, before) = (self.chunks.len().checked_sub(1)?, self.chunks.view_drop_right(1).fold());
+            Some((last, byte - before.bytes, self.chunks[last].value().as_str(), before))
+        });
+        let len = self.len_bytes();
+        match located {
+            Some(located) if byte <= len => {
+                if !located.2.is_char_boundary(located.1) {
+                    panic!("Byte index {} is not a char boundary", byte);
+                }
+                located
+            },
+            _ if byte == 0 => (0, 0, "", TextSummary::default()),
+            _ => panic!("Index out of bounds: the index is {} but the length is {}", byte, len),
+        }
+    }
+
+    /// Insert `text` at byte offset `byte`, shifting everything after it to the right.
+    ///
+    /// Panics if `byte` is greater than [`len_bytes`](Rope::len_bytes) or isn't on a char boundary.
+    pub fn insert_str(&mut self, byte: usize, text: &str) {
+        let (index, offset, chunk, _) = self.locate_position(byte);
+        if text.is_empty() {
+            return;
+        }
+        if self.chunks.is_empty() {
+            self.append_chunks(text);
+        } else if chunk.len() + text.len() <= MAX_CHUNK_LEN {
+            self.chunks.update_at(index, |chunk| chunk.update(&TextMetrics, |s| s.insert_str(offset, text)));
+        } else {
+            let old = self.chunks.remove_at(index).into_value();
+            let mut joined = String::with_capacity(old.len() + text.len());
+            joined.push_str(&old[..offset]);
+            joined.push_str(text);
+            joined.push_str(&old[offset..]);
+            let tail = self.chunks.split_off(index);
+            self.append_chunks(&joined);
+            self.chunks.append_all_right(tail);
+        }
+    }
+
+    /// Insert `ch` at byte offset `byte`, like [`insert_str`](Rope::insert_str).
+    pub fn insert_char(&mut self, byte: usize, ch: char) {
+        self.insert_str(byte, ch.encode_utf8(&mut [0; 4]));
+    }
+
+    /// Append `text` to the end of this rope.
+    pub fn push_str(&mut self, text: &str) {
+        self.insert_str(self.len_bytes(), text);
+    }
+
+    //appends `text` as new chunks, splitting it evenly at char boundaries, so that none of them is much less than half full
+    fn append_chunks(&mut self, mut text: &str) {
+        let settings = self.chunks.get_settings();
+        let pieces = core::iter::from_fn(|| {
+            if text.is_empty() {
+                return None;
+            }
+            let mut split = text.len().div_ceil(text.len().div_ceil(MAX_CHUNK_LEN));
+            while !text.is_char_boundary(split) {
+                split -= 1;
+            }
+            let (piece, rest) = text.split_at(split);
+            text = rest;
+            Some(settings.wrap(String::from(piece)))
+        });
+        self.chunks.append_right_from_iter(pieces);
+    }
+
+    /// Remove the text in the byte range `range`.
+    ///
+    /// Panics if `range` is out of bounds, or if either end of it isn't on a char boundary.
+    pub fn remove(&mut self, range: impl RangeBounds<usize>) {
+        let (start, end) = range_to_start_end(range, self.len_bytes());
+        let (first, start_offset, _, _) = self.locate_position(start);
+        let (last, end_offset, _, _) = self.locate_position(end);
+        if start == end {
+            return;
+        }
+        if first == last {
+            self.chunks.update_at(first, |chunk| chunk.update(&TextMetrics, |s| s.replace_range(start_offset..end_offset, "")));
+        } else {
+            self.chunks.update_at(last, |chunk| chunk.update(&TextMetrics, |s| s.replace_range(..end_offset, "")));
+            self.chunks.mut_view_range(first + 1..last).take_all();
+            self.chunks.update_at(first, |chunk| chunk.update(&TextMetrics, |s| s.truncate(start_offset)));
+            //the rest of the last chunk is right after the first now
+            self.tidy(first + 1);
+        }
+        self.tidy(first);
+    }
+
+    //removes the chunk at `index` if it's empty, or if it's less than half full, merges it with its smaller neighbor,
+    //or splits the two evenly if together they're too long for one chunk
+    fn tidy(&mut self, index: usize) {
+        if index >= self.chunks.len() {
+            return;
+        }
+        let len = self.chunks[index].value().len();
+        if len == 0 {
+            self.chunks.remove_at(index);
+            return;
+        }
+        if len >= MAX_CHUNK_LEN / 2 {
+            return;
+        }
+        let before = index.checked_sub(1).map(|i| self.chunks[i].value().len());
+        let after = self.chunks.try_get(index + 1).ok().map(|chunk| chunk.value().len());
+        //the index of the left one of the two chunks to merge
+        let left = match (before, after) {
+            (Some(before), Some(after)) if before <= after => index - 1,
+            (Some(_), None) => index - 1,
+            (_, Some(_)) => index,
+            (None, None) => return,
+        };
+        let right = self.chunks.remove_at(left + 1).into_value();
+        let rest = self.chunks.update_at(left, |chunk| chunk.update(&TextMetrics, |s| {
+            s.push_str(&right);
+            (s.len() > MAX_CHUNK_LEN).then(|| {
+                let mut split = s.len() / 2;
+                while !s.is_char_boundary(split) {
+                    split -= 1;
+                }
+                s.split_off(split)
+            })
+        }));
+        if let Some(rest) = rest {
+            let settings = self.chunks.get_settings();
+            self.chunks.insert_at(left + 1, settings.wrap(rest));
+        }
+    }
+
+    /// Get the char offset of the byte offset `byte`.
+    ///
+    /// Panics if `byte` is greater than [`len_bytes`](Rope::len_bytes) or isn't on a char boundary.
+    pub fn byte_to_char(&self, byte: usize) -> usize {
+        let (_, offset, chunk, before) = self.locate_position(byte);
+        before.chars + chunk[..offset].chars().count()
+    }
+
+    /// Get the byte offset of the char offset `char_index`.
+    ///
+    /// Panics if `char_index` is greater than [`len_chars`](Rope::len_chars).
+    pub fn char_to_byte(&self, char_index: usize) -> usize {
+        let summary = self.summary();
+        if char_index == summary.chars {
+            return summary.bytes;
+        }
+        let Some((_, chunk, before)) = self.chunks.find_left(|s| s.chars > char_index) else {
+            panic!("Index out of bounds: the index is {} but the length is {}", char_index, summary.chars);
+        };
+        before.bytes + chunk.value().char_indices().nth(char_index - before.chars).unwrap().0
+    }
+
+    /// Get the [`char`] at char offset `char_index`.
+    ///
+    /// Panics if `char_index` is out of bounds.
+    pub fn char(&self, char_index: usize) -> char {
+        let Some((_, chunk, before)) = self.chunks.find_left(|s| s.chars > char_index) else {
+            panic!("Index out of bounds: the index is {} but the length is {}", char_index, self.len_chars());
+        };
+        chunk.value().chars().nth(char_index - before.chars).unwrap()
+    }
+
+    /// Get the line that byte offset `byte` is on, counting from 0.
+    ///
+    /// Panics if `byte` is greater than [`len_bytes`](Rope::len_bytes) or isn't on a char boundary.
+    pub fn byte_to_line(&self, byte: usize) -> usize {
+        let (_, offset, chunk, before) = self.locate_position(byte);
+        before.newlines + chunk.as_bytes()[..offset].iter().filter(|&&b| b == b'\n').count()
+    }
+
+    /// Get the byte offset of the start of line `line`, counting from 0.
+    ///
+    /// Panics if `line` isn't less than [`len_lines`](Rope::len_lines).
+    pub fn line_to_byte(&self, line: usize) -> usize {
+        if line == 0 {
+            return 0;
+        }
+        //the chunk holding the newline which ends the previous line
+        let Some((_, chunk, before)) = self.chunks.find_left(|s| s.newlines >= line) else {
+            panic!("Index out of bounds: the line is {} but the number of lines is {}", line, self.len_lines());
+        };
+        let (newline, _) = chunk.value().match_indices('\n').nth(line - 1 - before.newlines).unwrap();
+        before.bytes + newline + 1
+    }
+
+    /// Get the line and column of byte offset `byte`, both counting from 0. The column is counted in [`char`]s from the start of the line.
+    ///
+    /// Panics if `byte` is greater than [`len_bytes`](Rope::len_bytes) or isn't on a char boundary.
+    pub fn byte_to_line_col(&self, byte: usize) -> (usize, usize) {
+        let line = self.byte_to_line(byte);
+        (line, self.byte_to_char(byte) - self.byte_to_char(self.line_to_byte(line)))
+    }
+
+    /// Get the byte offset of column `col` of line `line`, both counting from 0, where the column is counted in [`char`]s. This is the inverse of [`byte_to_line_col`](Rope::byte_to_line_col).
+    ///
+    /// Panics if `line` isn't less than [`len_lines`](Rope::len_lines), or if `col` is past the end of the line (not counting its newline).
+    pub fn line_col_to_byte(&self, line: usize, col: usize) -> usize {
+        let line_start = self.byte_to_char(self.line_to_byte(line));
+        //the end of the line, before its newline if it has one
+        let line_end = if line + 1 < self.len_lines() {self.byte_to_char(self.line_to_byte(line + 1)) - 1} else {self.len_chars()};
+        if col > line_end - line_start {
+            panic!("Index out of bounds: the column is {} but the length of line {} is {}", col, line, line_end - line_start);
+        }
+        self.char_to_byte(line_start + col)
+    }
+
+    /// Get a slice of the line `line`, counting from 0, including its newline if it has one.
+    ///
+    /// Panics if `line` isn't less than [`len_lines`](Rope::len_lines).
+    pub fn line(&self, line: usize) -> RopeSlice<'_> {
+        let start = self.line_to_byte(line);
+        let end = if line + 1 < self.len_lines() {self.line_to_byte(line + 1)} else {self.len_bytes()};
+        RopeSlice { rope: self, start, end }
+    }
+
+    /// Get a slice of the text in the byte range `range`.
+    ///
+    /// Panics if `range` is out of bounds, or if either end of it isn't on a char boundary.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> RopeSlice<'_> {
+        let (start, end) = range_to_start_end(range, self.len_bytes());
+        self.locate_position(start);
+        self.locate_position(end);
+        RopeSlice { rope: self, start, end }
+    }
+
+    /// Iterate over the chunks of this rope, from left to right. Each chunk is a `&str` of at most a few hundred bytes.
+    pub fn chunks(&self) -> Chunks<'_> {
+        self.slice(..).chunks()
+    }
+}
+
+impl From<&str> for Rope {
+    fn from(text: &str) -> Self {
+        let mut ret = Self::new();
+        ret.append_chunks(text);
+        ret
+    }
+}
+
+impl From<String> for Rope {
+    fn from(text: String) -> Self {
+        Self::from(text.as_str())
+    }
+}
+
+impl fmt::Display for Rope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.slice(..).fmt(f)
+    }
+}
+
+impl fmt::Debug for Rope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.slice(..).fmt(f)
+    }
+}
+
+impl PartialEq for Rope {
+    fn eq(&self, other: &Self) -> bool {
+        self.slice(..) == other.slice(..)
+    }
+}
+
+impl Eq for Rope {}
+
+impl PartialEq<str> for Rope {
+    fn eq(&self, other: &str) -> bool {
+        self.slice(..) == *other
+    }
+}
+
+impl PartialEq<&str> for Rope {
+    fn eq(&self, other: &&str) -> bool {
+        self.slice(..) == **other
+    }
+}
+
+/// An immutable view of a byte range of a [`Rope`], such as one of its [`line`](Rope::line)s.
+#[derive(Clone,Copy)]
+pub struct RopeSlice<'a> {
+    rope: &'a Rope,
+    start: usize,
+    end: usize,
+}
+
+impl<'a> RopeSlice<'a> {
+    /// Get the byte range of the rope which this slice views.
+    pub fn byte_range(&self) -> core::ops::Range<usize> {
+        self.start..self.end
+    }
+
+    /// Get the length of this slice in bytes.
+    pub fn len_bytes(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Returns `true` if this slice has no text.
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Iterate over the parts of the rope's chunks which are in this slice, from left to right.
+    pub fn chunks(&self) -> Chunks<'a> {
+        let chunks = &self.rope.chunks;
+        if self.is_empty() {
+            return Chunks { iter: chunks.view_range(0..0).as_sized_chain().iter_consume(), skip: 0, remaining: 0 };
+        }
+        let (first, skip, _, _) = self.rope.locate_position(self.start);
+        let (last, _, _, _) = self.rope.locate_byte(self.end - 1).unwrap();
+        Chunks { iter: chunks.view_range(first..=last).as_sized_chain().iter_consume(), skip, remaining: self.len_bytes() }
+    }
+}
+
+impl fmt::Display for RopeSlice<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.chunks().try_for_each(|chunk| f.write_str(chunk))
+    }
+}
+
+impl fmt::Debug for RopeSlice<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("\"")?;
+        self.chunks().try_for_each(|chunk| write!(f, "{}", chunk.escape_debug()))?;
+        f.write_str("\"")
+    }
+}
+
+impl PartialEq for RopeSlice<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len_bytes() == other.len_bytes() && self.chunks().flat_map(str::bytes).eq(other.chunks().flat_map(str::bytes))
+    }
+}
+
+impl Eq for RopeSlice<'_> {}
+
+impl PartialEq<str> for RopeSlice<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self.len_bytes() == other.len() && self.chunks().flat_map(str::bytes).eq(other.bytes())
+    }
+}
+
+impl PartialEq<&str> for RopeSlice<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+/// An iterator over the chunks of a [`Rope`] or [`RopeSlice`], as `&str`s. Made by [`Rope::chunks`] or [`RopeSlice::chunks`].
+pub struct Chunks<'a> {
+    iter: Iter<'a, False, Chunk, (usize,TextSummary)>,
+    //how many bytes to skip at the start of the next chunk
+    skip: usize,
+    remaining: usize,
+}
+
+impl<'a> Iterator for Chunks<'a> {
+    type Item = &'a str;
+    fn next(&mut self) -> Option<&'a str> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let chunk = &self.iter.next()?.value()[self.skip..];
+        let chunk = &chunk[..chunk.len().min(self.remaining)];
+        self.skip = 0;
+        self.remaining -= chunk.len();
+        Some(chunk)
+    }
+}
+
+impl core::iter::FusedIterator for Chunks<'_> {}
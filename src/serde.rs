@@ -0,0 +1,135 @@
+use core::{fmt, marker::PhantomData};
+
+use ::serde::{de::{DeserializeSeed, SeqAccess, Visitor}, ser::SerializeSeq, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{fold_chain::FoldChain, fold_list::FoldList, fold_settings::FoldSettings};
+
+//serializes the elements as a sequence of known length, since some formats need the length up front
+fn serialize_elements<'a, T: Serialize + 'a, S: Serializer>(serializer: S, len: usize, iter: impl Iterator<Item=&'a T>) -> Result<S::Ok, S::Error> {
+    let mut seq = serializer.serialize_seq(Some(len))?;
+    for t in iter {
+        seq.serialize_element(t)?;
+    }
+    seq.end()
+}
+
+impl<T: Serialize, D: Clone, Settings: FoldSettings<T,D>> Serialize for FoldList<T, D, Settings> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_elements(serializer, self.len(), self.iter())
+    }
+}
+
+impl<T: Serialize, D: Clone, Settings: FoldSettings<T,D>> Serialize for FoldChain<T, D, Settings> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_elements(serializer, self.iter().count(), self.iter())
+    }
+}
+
+//feeds the elements of a sequence to `f` as an iterator, which stops at the first error
+fn with_seq_iter<'de, T: Deserialize<'de>, A: SeqAccess<'de>, R>(mut seq: A, f: impl FnOnce(&mut dyn Iterator<Item=T>) -> R) -> Result<R, A::Error> {
+    let mut err = None;
+    let mut iter = core::iter::from_fn(|| match seq.next_element() {
+        Ok(t) => t,
+        Err(e) => {
+            err = Some(e);
+            None
+        }
+    });
+    let ret = f(&mut iter);
+    match err {
+        Some(e) => Err(e),
+        None => Ok(ret),
+    }
+}
+
+/// A [`DeserializeSeed`] which deserializes a sequence of elements into a [`FoldList`] with the given [Settings](crate#fold-settings).
+///
+/// When `Settings` implements [`Default`], `FoldList` also implements [`Deserialize`] directly.
+pub struct FoldListSeed<T,D,Settings> {
+    /// The settings of the resulting list
+    pub settings: Settings,
+
+    #[allow(missing_docs)]
+    pub _m: PhantomData<fn(T)->D>
+}
+
+impl<T, D, Settings> FoldListSeed<T, D, Settings> {
+    /// Create a seed which will deserialize a `FoldList` with these settings.
+    pub fn new(settings: Settings) -> Self {
+        Self { settings, _m: PhantomData }
+    }
+}
+
+impl<'de, T: Deserialize<'de>, D: Clone, Settings: FoldSettings<T,D>> DeserializeSeed<'de> for FoldListSeed<T, D, Settings> {
+    type Value = FoldList<T, D, Settings>;
+
+    fn deserialize<De: Deserializer<'de>>(self, deserializer: De) -> Result<Self::Value, De::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, T: Deserialize<'de>, D: Clone, Settings: FoldSettings<T,D>> Visitor<'de> for FoldListSeed<T, D, Settings> {
+    type Value = FoldList<T, D, Settings>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        let mut ret = FoldList::from_settings(self.settings);
+        with_seq_iter(seq, |iter| ret.append_right_from_iter(iter))?;
+        Ok(ret)
+    }
+}
+
+impl<'de, T: Deserialize<'de>, D: Clone, Settings: FoldSettings<T,D> + Default> Deserialize<'de> for FoldList<T, D, Settings> {
+    fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        FoldListSeed::new(Settings::default()).deserialize(deserializer)
+    }
+}
+
+/// A [`DeserializeSeed`] which deserializes a sequence of elements into a [`FoldChain`] with the given [Settings](crate#fold-settings).
+///
+/// When `Settings` implements [`Default`], `FoldChain` also implements [`Deserialize`] directly.
+pub struct FoldChainSeed<T,D,Settings> {
+    /// The settings of the resulting chain
+    pub settings: Settings,
+
+    #[allow(missing_docs)]
+    pub _m: PhantomData<fn(T)->D>
+}
+
+impl<T, D, Settings> FoldChainSeed<T, D, Settings> {
+    /// Create a seed which will deserialize a `FoldChain` with these settings.
+    pub fn new(settings: Settings) -> Self {
+        Self { settings, _m: PhantomData }
+    }
+}
+
+impl<'de, T: Deserialize<'de>, D: Clone, Settings: FoldSettings<T,D>> DeserializeSeed<'de> for FoldChainSeed<T, D, Settings> {
+    type Value = FoldChain<T, D, Settings>;
+
+    fn deserialize<De: Deserializer<'de>>(self, deserializer: De) -> Result<Self::Value, De::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, T: Deserialize<'de>, D: Clone, Settings: FoldSettings<T,D>> Visitor<'de> for FoldChainSeed<T, D, Settings> {
+    type Value = FoldChain<T, D, Settings>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        let mut ret = FoldChain::from_settings(self.settings);
+        with_seq_iter(seq, |iter| ret.append_right_from_iter(iter))?;
+        Ok(ret)
+    }
+}
+
+impl<'de, T: Deserialize<'de>, D: Clone, Settings: FoldSettings<T,D> + Default> Deserialize<'de> for FoldChain<T, D, Settings> {
+    fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        FoldChainSeed::new(Settings::default()).deserialize(deserializer)
+    }
+}
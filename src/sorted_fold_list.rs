@@ -0,0 +1,253 @@
+use core::{marker::PhantomData, ops::{Bound, RangeBounds}};
+
+use crate::{fold_chain::{ImmFoldChainSliceStruct, Iter}, fold_list::{FoldList, FoldListSlice, FoldListSliceFrom}, fold_settings::{FoldSettings, FoldSettingsStruct, SettingsWithSize}, fold_simplification::{OpFromSettings, SimplificationWithoutShortcut, SizeIgnoreFn}, misc::{EmptyFn, False, TupleFun}};
+
+/// A wrapper around a [`FoldSettings`] value which also keeps track of the largest key of a sorted range, by replacing its element type, `T`, with `(K,T)`, and its delta type, `D`, with `(Option<K>,D)`.
+///
+/// Since the elements of a [`SortedFoldList`] are sorted by key, the largest key of a range is just its rightmost key, so the `Option<K>` part of the delta behaves as follows:
+///   * `op` keeps the right key, unless it's [`None`]
+///   * `delta_of` is the element's key
+///   * `empty` is [`None`]
+#[derive(Clone,Copy,Default)]
+pub struct SortedSettings<S>(pub S);
+impl<K: Clone, T, D: Clone, S: FoldSettings<T,D>> FoldSettings<(K,T),(Option<K>,D)> for SortedSettings<S> {
+    fn op(&self, (ka,a): (Option<K>,D), (kb,b): (Option<K>,D)) -> (Option<K>,D) {
+        (kb.or(ka), self.0.op(a,b))
+    }
+    fn delta_of(&self, (k,t): &(K,T)) -> (Option<K>,D) {
+        (Some(k.clone()), self.0.delta_of(t))
+    }
+    fn empty(&self) -> (Option<K>,D) {
+        (None, self.0.empty())
+    }
+}
+
+/// The type of the views returned by [`SortedFoldList::range`]: a [`FoldListSlice`] of `(K,T)` pairs, whose fold is that of the list's own `Settings`.
+pub type SortedRange<'a,K,T,D,Settings> = FoldListSliceFrom<'a, (K,T), (Option<K>,D), SortedSettings<Settings>,
+    SimplificationWithoutShortcut<(K,T), (Option<K>,D), D, SizeIgnoreFn, OpFromSettings<T,D,Settings>>,
+    ImmFoldChainSliceStruct<'a, False, False, False, SettingsWithSize<SortedSettings<Settings>>, (), (K,T), (usize,(Option<K>,D))>>;
+
+/// A [`FoldList`] of `(K,T)` pairs which is always sorted by key, so that it can be used as an order-statistic multiset (or multimap).
+///
+/// Elements with equal keys are kept in the order they were inserted. Finding, inserting and removing by key, [`rank`](SortedFoldList::rank) and [`select`](SortedFoldList::select) are all *O*(log(n)),
+/// and [`range`](SortedFoldList::range) gives a view of any range of keys, whose fold is that of `Settings`.
+///
+/// `K`'s [`Ord`] implementation should be consistent, as for [`BTreeMap`](alloc::collections::BTreeMap), or else the elements may not be found again.
+pub struct SortedFoldList<K: Clone, T, D: Clone, Settings: FoldSettings<T,D>> {
+    list: FoldList<(K,T), (Option<K>,D), SortedSettings<Settings>>,
+}
+
+impl<K: Ord + Clone, T, D: Clone, OP: Fn(D,D)->D + Copy, DeltaOf: Fn(&T)->D + Copy, Empty: Fn()->D + Copy> SortedFoldList<K,T,D,FoldSettingsStruct<T,D,TupleFun<OP>,DeltaOf,EmptyFn<Empty>>> {
+    /// Create a new empty `SortedFoldList`, given the closures for [Settings](crate#fold-settings), which see only the `T` part of each element.
+    pub fn new(op: OP, delta_of: DeltaOf, empty_delta_fn: Empty) -> Self {
+        SortedFoldList::from_settings(FoldSettingsStruct {
+            op_closure: TupleFun(op),
+            t2d_closure: delta_of,
+            empty_closure: EmptyFn(empty_delta_fn),
+            _m: PhantomData,
+        })
+    }
+}
+
+impl<K: Ord + Clone, T, D: Clone, Settings: FoldSettings<T,D>> SortedFoldList<K, T, D, Settings> {
+    /// Create a new empty `SortedFoldList`, with the specified [Settings](crate#fold-settings), which see only the `T` part of each element.
+    pub fn from_settings(settings: Settings) -> Self {
+        Self { list: FoldList::from_settings(SortedSettings(settings)) }
+    }
+
+    /// Get the [Settings](crate#fold-settings) of this list.
+    pub fn get_settings(&self) -> Settings {
+        self.list.get_settings().0
+    }
+
+    /// Get the `FoldList` which underlies this list, e.g. to perform searches by position.
+    pub fn as_fold_list(&self) -> &FoldList<(K,T), (Option<K>,D), SortedSettings<Settings>> {
+        &self.list
+    }
+
+    /// Get the number of elements in this list.
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Returns `true` if this list has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Get the fold of every element in this list.
+    pub fn fold(&self) -> D {
+        self.list.fold().1
+    }
+
+    //the number of elements whose keys don't satisfy `predicate`, which must be monotonic in the key
+    fn count_until(&self, predicate: impl Fn(&K)->bool) -> usize {
+        self.list
+            .view_simplify_with_shortcut(
+                |(k,_): &(Option<K>,D)| k.clone(),
+                |(a,b): (Option<K>,Option<K>)| b.or(a),
+                |()| None,
+                |(k,_): &(K,T)| Some(k.clone())
+            )
+            .view_take_left_until(|k: &Option<K>| k.as_ref().is_some_and(&predicate))
+            .len()
+    }
+
+    /// Get the number of elements whose keys are less than `key`. This is the index where `key` would be inserted, if it were inserted before elements with the same key.
+    pub fn rank(&self, key: &K) -> usize {
+        self.count_until(|k| k >= key)
+    }
+
+    //the number of elements whose keys are less than or equal to `key`
+    fn rank_after(&self, key: &K) -> usize {
+        self.count_until(|k| k > key)
+    }
+
+    /// Get the element at index `index`, in order of keys, or [`None`] if `index` is out of bounds.
+    pub fn select(&self, index: usize) -> Option<(&K, &T)> {
+        self.list.try_get(index).ok().map(|(k,t)| (k,t))
+    }
+
+    /// Get the leftmost element with key `key`, or [`None`] if there is none.
+    pub fn get(&self, key: &K) -> Option<&T> {
+        match self.select(self.rank(key)) {
+            Some((k,t)) if k == key => Some(t),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if some element has key `key`.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Get the number of elements with key `key`.
+    pub fn count(&self, key: &K) -> usize {
+        self.rank_after(key) - self.rank(key)
+    }
+
+    /// Get the element with the smallest key, or [`None`] if this list is empty.
+    pub fn first(&self) -> Option<(&K, &T)> {
+        self.list.left().map(|(k,t)| (k,t))
+    }
+
+    /// Get the element with the largest key, or [`None`] if this list is empty.
+    pub fn last(&self) -> Option<(&K, &T)> {
+        self.list.right().map(|(k,t)| (k,t))
+    }
+
+    /// Insert `value` with key `key`, after every element with an equal key, and return the index where it was inserted.
+    pub fn insert(&mut self, key: K, value: T) -> usize {
+        let index = self.rank_after(&key);
+        self.list.insert_at(index, (key, value));
+        index
+    }
+
+    /// Remove and return the leftmost element with key `key`, or return [`None`] if there is none.
+    pub fn remove(&mut self, key: &K) -> Option<T> {
+        let index = self.rank(key);
+        match self.list.try_get(index) {
+            Ok((k,_)) if k == key => Some(self.list.remove_at(index).1),
+            _ => None,
+        }
+    }
+
+    /// Remove and return the element at index `index`, in order of keys.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove_at(&mut self, index: usize) -> (K, T) {
+        self.list.remove_at(index)
+    }
+
+    /// Remove and return the element with the smallest key, or return [`None`] if this list is empty.
+    pub fn pop_first(&mut self) -> Option<(K, T)> {
+        self.list.pop_left()
+    }
+
+    /// Remove and return the element with the largest key, or return [`None`] if this list is empty.
+    pub fn pop_last(&mut self) -> Option<(K, T)> {
+        self.list.pop_right()
+    }
+
+    /// Mutate the value (but not the key) of the element at index `index` via a closure, and return the result of the closure.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn update_at<R>(&mut self, index: usize, f: impl FnOnce(&mut T)->R) -> R {
+        self.list.update_at(index, |(_,t)| f(t))
+    }
+
+    /// Get the range of indices of the elements whose keys are in `range`.
+    pub fn index_range(&self, range: impl RangeBounds<K>) -> core::ops::Range<usize> {
+        let start = match range.start_bound() {
+            Bound::Included(k) => self.rank(k),
+            Bound::Excluded(k) => self.rank_after(k),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(k) => self.rank_after(k),
+            Bound::Excluded(k) => self.rank(k),
+            Bound::Unbounded => self.len(),
+        };
+        start..end.max(start)
+    }
+
+    /// Get a view of the elements whose keys are in `range`. Its fold is that of `Settings`, so e.g. ```list.range(a..b).fold()``` folds the values with keys from `a` to `b`.
+    pub fn range(&self, range: impl RangeBounds<K>) -> SortedRange<'_, K, T, D, Settings> {
+        let settings = self.get_settings();
+        self.list
+            .view_range(self.index_range(range))
+            .view_simplify(SizeIgnoreFn, OpFromSettings { settings, _m: PhantomData })
+    }
+
+    /// Get the fold of the elements whose keys are in `range`.
+    pub fn fold_range(&self, range: impl RangeBounds<K>) -> D {
+        self.range(range).fold()
+    }
+
+    /// Iterate over the elements of this list, in order of keys.
+    pub fn iter(&self) -> Iter<'_, False, (K,T), (usize,(Option<K>,D))> {
+        self.list.iter()
+    }
+}
+
+impl<K: Clone, T: Clone, D: Clone, Settings: FoldSettings<T,D>> Clone for SortedFoldList<K, T, D, Settings> {
+    fn clone(&self) -> Self {
+        Self { list: self.list.clone() }
+    }
+}
+
+impl<K: core::fmt::Debug + Clone, T: core::fmt::Debug, D: Clone, Settings: FoldSettings<T,D>> core::fmt::Debug for SortedFoldList<K, T, D, Settings> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.list.fmt(f)
+    }
+}
+
+impl<K: Ord + Clone, T, D: Clone, Settings: FoldSettings<T,D> + Default> Default for SortedFoldList<K, T, D, Settings> {
+    fn default() -> Self {
+        Self::from_settings(Settings::default())
+    }
+}
+
+impl<K: Ord + Clone, T, D: Clone, Settings: FoldSettings<T,D> + Default> FromIterator<(K,T)> for SortedFoldList<K, T, D, Settings> {
+    fn from_iter<I: IntoIterator<Item = (K,T)>>(iter: I) -> Self {
+        let mut ret = Self::default();
+        ret.extend(iter);
+        ret
+    }
+}
+
+impl<K: Ord + Clone, T, D: Clone, Settings: FoldSettings<T,D>> Extend<(K,T)> for SortedFoldList<K, T, D, Settings> {
+    fn extend<I: IntoIterator<Item = (K,T)>>(&mut self, iter: I) {
+        for (k,t) in iter {
+            self.insert(k, t);
+        }
+    }
+}
+
+impl<'a, K: Ord + Clone, T, D: Clone, Settings: FoldSettings<T,D>> IntoIterator for &'a SortedFoldList<K, T, D, Settings> {
+    type Item = &'a (K,T);
+    type IntoIter = Iter<'a, False, (K,T), (usize,(Option<K>,D))>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
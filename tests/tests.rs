@@ -1,8 +1,8 @@
 #[cfg(test)]
 mod vec_based_fold_chain_slice;
-use foldlist::{fold_chain::{FoldChain, FoldChainSlice, ImmFoldChainSliceStruct, MutFoldChainSlice, MutFoldChainSliceStruct}, fold_list::{FoldList, FoldListSlice, FoldListSliceFrom, MutFoldListSlice}, fold_settings::{FoldSettings, FoldSettingsStruct, SettingsWithSize}, fold_simplification::FoldSimplification, misc::{Bool,TupleFun}};
+use foldlist::{chunked::ChunkedFoldList, fold_map::{Entry, FoldMap}, fold_window::FoldWindow, rope::Rope, sorted_fold_list::SortedFoldList, fold_chain::{FoldChain, FoldChainSlice, ImmFoldChainSliceStruct, MutFoldChainSlice, MutFoldChainSliceStruct}, fold_list::{FoldList, FoldListSlice, FoldListSliceFrom, MutFoldListSlice}, fold_settings::{CachedDelta, CachedDeltaSettings, FoldSettings, FoldSettingsStruct, InvertibleFoldSettings, InvertibleFoldSettingsStruct, SettingsWithSize}, fold_simplification::{FoldSimplification, SimplificationWithShortcut, SimplificationWithoutShortcut}, laws::{self, SettingsLawViolation, SimplificationLawViolation}, misc::{Bool,IndexOutOfBounds,TupleFun}, monoids, node_pool::NodePool};
 use rand::Rng;
-use std::{cell::RefCell, fmt::Debug, io::Write, marker::PhantomData, rc::Rc};
+use std::{cell::RefCell, fmt::Debug, io::Write, marker::PhantomData, panic::{catch_unwind, AssertUnwindSafe}, rc::Rc};
 use rand::{SeedableRng, rngs::StdRng};
 
 use crate::vec_based_fold_chain_slice::VecBasedFoldChainSlice;
@@ -1224,6 +1224,1213 @@ fn fc_imm_do_tests<'a,T: PartialEq + Eq + Debug + Clone + 'a, D: Clone, D2: Clon
     }
 }
 
+#[test]
+fn test_split_off() {
+    let mut rng = StdRng::seed_from_u64(3003);
+    for _ in 0..200 {
+        let len = rng.random_range(0..100);
+        let vec: Vec<usize> = (0..len).map(|_| rng.random_range(0..10)).collect();
+        let mut list = FoldList::from_iter(|a,b| a+b, |x: &usize| *x, || 0, vec.iter().copied());
+        let index = rng.random_range(0..=len);
+        let right = list.split_off(index);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec[..index]);
+        assert_eq!(right.iter().copied().collect::<Vec<_>>(), vec[index..]);
+        assert_eq!(list.fold() + right.fold(), vec.iter().sum());
+        assert!((&list).debug_check_structural_integrity());
+        assert!((&right).debug_check_structural_integrity());
+
+        let mut chain = FoldChain::from_iter(|a,b| a+b, |x: &usize| *x, || 0, vec.iter().copied());
+        let threshold = rng.random_range(0..=vec.iter().sum::<usize>() + 1);
+        let right = chain.split_off_where(|sum| *sum >= threshold);
+        let split_at = (1..=len).find(|i| vec[..*i].iter().sum::<usize>() >= threshold).map_or(len, |i| i - 1);
+        assert_eq!(chain.iter().copied().collect::<Vec<_>>(), vec[..split_at]);
+        assert_eq!(right.iter().copied().collect::<Vec<_>>(), vec[split_at..]);
+    }
+}
+
+#[test]
+#[should_panic(expected = "Index out of bounds: the index is 4 but the length is 3")]
+fn test_split_off_out_of_bounds() {
+    let mut list = FoldList::from_iter(|a,b| a+b, |x: &usize| *x, || 0, [1,2,3].into_iter());
+    list.split_off(4);
+}
+
+#[test]
+fn test_mut_split() {
+    let mut rng = StdRng::seed_from_u64(4004);
+    for _ in 0..200 {
+        let len = rng.random_range(0..60);
+        let mut vec: Vec<usize> = (0..len).map(|_| rng.random_range(0..10)).collect();
+        let mut list = FoldList::from_iter(|a,b| a+b, |x: &usize| *x, || 0, vec.iter().copied());
+        let start = rng.random_range(0..=len);
+        let end = rng.random_range(start..=len);
+        let index = rng.random_range(0..=end-start);
+        let reversed = rng.random_bool(0.5);
+        let (expected_left, expected_right) = (&vec[start..start+index], &vec[start+index..end]);
+        let view = list.mut_view_drop_left(start).mut_view_take_left(end - start);
+        if reversed {
+            //regardless of the view's direction, the halves hold their elements in the base's order
+            let mut split = view.mut_view_reversed().mut_split_at(end - start - index);
+            mut_split_edit_halves(&mut split.right, &mut split.left, expected_left, expected_right);
+        } else {
+            let mut split = view.mut_split_at(index);
+            mut_split_edit_halves(&mut split.left, &mut split.right, expected_left, expected_right);
+        }
+        let mut expected = vec[start..start+index].to_vec();
+        expected.push(100);
+        if let Some(x) = expected.first_mut() {*x += 1}
+        expected.push(200);
+        expected.extend_from_slice(&vec[start+index..end]);
+        expected.pop();
+        vec.splice(start..end, expected);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec);
+        assert_eq!(list.fold(), vec.iter().sum());
+        assert!((&list).debug_check_structural_integrity());
+    }
+
+    let mut list = FoldList::from_iter(|a,b| a+b, |x: &usize| *x, || 0, 1..=10);
+    let split = list.mut_split_where(|sum| *sum > 10);
+    assert_eq!(split.left.iter().copied().collect::<Vec<_>>(), vec![1,2,3,4]);
+    assert_eq!(split.right.fold(), 45);
+    drop(split);
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), (1..=10).collect::<Vec<_>>());
+}
+
+fn mut_split_edit_halves<S: FoldSettings<usize,usize>>(left: &mut FoldList<usize,usize,S>, right: &mut FoldList<usize,usize,S>, expected_left: &[usize], expected_right: &[usize]) {
+    assert_eq!(left.iter().copied().collect::<Vec<_>>(), expected_left);
+    assert_eq!(right.iter().copied().collect::<Vec<_>>(), expected_right);
+    left.append_right(100);
+    right.append_left(200);
+    assert_eq!(right.pop_right(), Some(*expected_right.last().unwrap_or(&200)));
+    left.update_left(|x| x.map(|x| *x += 1));
+    assert_eq!(left.fold(), expected_left.iter().sum::<usize>() + 101);
+}
+
+#[test]
+fn test_cursors() {
+    let mut rng = StdRng::seed_from_u64(5005);
+    for _ in 0..100 {
+        let len = rng.random_range(0..40);
+        let mut vec: Vec<usize> = (0..len).map(|_| rng.random_range(0..10)).collect();
+        let mut list = FoldList::from_iter(|a,b| a+b, |x: &usize| *x, || 0, vec.iter().copied());
+
+        let mut cursor = list.cursor_at(rng.random_range(0..=len));
+        for _ in 0..50 {
+            if rng.random_bool(0.5) {cursor.move_next()} else {cursor.move_prev()}
+            let index = cursor.index();
+            assert_eq!(cursor.current(), vec.get(index));
+            assert_eq!(cursor.prefix_fold(), vec[..index].iter().sum::<usize>());
+        }
+        assert_eq!(list.cursor_back().current(), vec.last());
+
+        let mut cursor = list.cursor_at_mut(rng.random_range(0..=len));
+        for _ in 0..100 {
+            let index = cursor.index();
+            match rng.random_range(0..7) {
+                0 => cursor.move_next(),
+                1 => cursor.move_prev(),
+                2 => {
+                    let value = rng.random_range(0..10);
+                    let result = cursor.replace_current(value);
+                    match vec.get_mut(index) {
+                        Some(x) => assert_eq!(result, Ok(core::mem::replace(x, value))),
+                        None => assert_eq!(result, Err(value)),
+                    }
+                },
+                3 => {
+                    cursor.update_current(|x| if let Some(x) = x {*x += 1});
+                    if let Some(x) = vec.get_mut(index) {*x += 1}
+                },
+                4 => {
+                    let value = rng.random_range(0..10);
+                    cursor.insert_before(value);
+                    vec.insert(index, value);
+                },
+                5 => {
+                    let value = rng.random_range(0..10);
+                    cursor.insert_after(value);
+                    if index == vec.len() {vec.insert(0, value)} else {vec.insert(index + 1, value)}
+                },
+                _ => assert_eq!(cursor.remove_current(), (index < vec.len()).then(|| vec.remove(index))),
+            }
+            let index = cursor.index();
+            assert_eq!(cursor.current(), vec.get(index));
+            assert_eq!(cursor.prefix_fold(), vec[..index].iter().sum::<usize>());
+        }
+        drop(cursor);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec);
+        assert_eq!(list.fold(), vec.iter().sum());
+        assert!((&list).debug_check_structural_integrity());
+    }
+}
+
+#[test]
+#[should_panic(expected = "Index out of bounds: the index is 4 but the length is 3")]
+fn test_cursor_out_of_bounds() {
+    let list = FoldList::from_iter(|a,b| a+b, |x: &usize| *x, || 0, [1,2,3].into_iter());
+    list.cursor_at(4);
+}
+
+#[test]
+fn test_view_range() {
+    let mut rng = StdRng::seed_from_u64(6006);
+    for _ in 0..200 {
+        let len = rng.random_range(0..60);
+        let mut vec: Vec<usize> = (0..len).map(|_| rng.random_range(0..10)).collect();
+        let mut list = FoldList::from_iter(|a,b| a+b, |x: &usize| *x, || 0, vec.iter().copied());
+        let start = rng.random_range(0..=len);
+        let end = rng.random_range(start..=len);
+
+        let view = list.view_range(start..end);
+        assert_eq!(view.iter().copied().collect::<Vec<_>>(), vec[start..end]);
+        assert_eq!(view.fold(), vec[start..end].iter().sum());
+        assert_eq!(list.view_range(..end).iter().copied().collect::<Vec<_>>(), vec[..end]);
+        assert_eq!(list.view_range(start..).iter().copied().collect::<Vec<_>>(), vec[start..]);
+        if end > start {
+            assert_eq!(list.view_range(start..=end-1).iter().copied().collect::<Vec<_>>(), vec[start..end]);
+        }
+
+        //the range is relative to the view, so a reversed view counts from the right
+        let reversed: Vec<usize> = vec.iter().rev().copied().collect();
+        assert_eq!(list.view_reversed().view_range(start..end).iter().copied().collect::<Vec<_>>(), reversed[start..end]);
+
+        let mut view = list.mut_view_range(start..end);
+        view.insert_at(0, 100);
+        view.pop_right();
+        vec.insert(start, 100);
+        vec.remove(end);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec);
+        assert_eq!(list.fold(), vec.iter().sum());
+        assert!((&list).debug_check_structural_integrity());
+    }
+}
+
+#[test]
+#[should_panic(expected = "Index out of bounds: the index is 4 but the length is 3")]
+fn test_view_range_out_of_bounds() {
+    let list = FoldList::from_iter(|a,b| a+b, |x: &usize| *x, || 0, [1,2,3].into_iter());
+    list.view_range(1..4);
+}
+
+#[test]
+#[should_panic(expected = "Invalid range: the range starts at 2 but ends at 1")]
+fn test_view_range_reversed() {
+    let list = FoldList::from_iter(|a,b| a+b, |x: &usize| *x, || 0, [1,2,3].into_iter());
+    #[allow(clippy::reversed_empty_ranges)]
+    list.view_range(2..1);
+}
+
+#[test]
+fn test_try_view_range() {
+    let mut list = FoldList::from_iter(|a,b| a+b, |x: &usize| *x, || 0, [1,2,3].into_iter());
+    assert_eq!(list.try_view_range(1..3).map(|v| v.fold()), Some(5));
+    assert_eq!(list.try_view_range(..=2).map(|v| v.fold()), Some(6));
+    assert_eq!(list.try_view_range(3..).map(|v| v.len()), Some(0));
+    assert!(list.try_view_range(1..4).is_none());
+    assert!(list.try_view_range((std::ops::Bound::Excluded(usize::MAX), std::ops::Bound::Unbounded)).is_none());
+    assert!(list.try_view_range(..=usize::MAX).is_none());
+    #[allow(clippy::reversed_empty_ranges)]
+    let reversed = list.try_mut_view_range(2..1);
+    assert!(reversed.is_none());
+    assert_eq!(list.try_mut_view_range(1..).unwrap().pop_left(), Some(2));
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1,3]);
+    assert_eq!(list.view_reversed().try_view_range(0..1).map(|v| v.fold()), Some(3));
+}
+
+#[test]
+fn test_try_indexing_at_usize_max() {
+    let mut list = FoldList::from_iter(|a,b| a+b, |x: &usize| *x, || 0, [1,2,3].into_iter());
+    let err = IndexOutOfBounds { index: usize::MAX, len: 3 };
+    assert_eq!(list.try_get(usize::MAX), Err(err));
+    assert!(list.try_get_mut(usize::MAX).is_err());
+    assert_eq!(list.try_update_at(usize::MAX, |x| *x), Err(err));
+    assert_eq!(list.try_set_at(usize::MAX, 0), Err(err));
+    assert_eq!(list.try_insert_at(usize::MAX, 0), Err(err));
+    assert_eq!(list.try_remove_at(usize::MAX), Err(err));
+    let err = IndexOutOfBounds { index: usize::MAX, len: 2 };
+    assert_eq!(list.mut_view_drop_left(1).try_remove_at(usize::MAX), Err(err));
+    assert_eq!(list.mut_view_reversed().mut_view_drop_left(1).try_insert_at(usize::MAX, 0), Err(err));
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1,2,3]);
+}
+
+#[test]
+fn test_try_indexing() {
+    let mut rng = StdRng::seed_from_u64(7007);
+    for _ in 0..100 {
+        let len = rng.random_range(0..30);
+        let mut vec: Vec<usize> = (0..len).map(|_| rng.random_range(0..10)).collect();
+        let mut list = FoldList::from_iter(|a,b| a+b, |x: &usize| *x, || 0, vec.iter().copied());
+        for _ in 0..30 {
+            let len = vec.len();
+            let index = rng.random_range(0..len + 3);
+            let value = rng.random_range(0..10);
+            let err = IndexOutOfBounds { index, len };
+            match rng.random_range(0..5) {
+                0 => {
+                    let expected = if index < len {Ok(vec[index] + 1)} else {Err(err)};
+                    if index < len {vec[index] += 1}
+                    assert_eq!(list.try_update_at(index, |x| {*x += 1; *x}), expected);
+                },
+                1 => {
+                    let expected = if index < len {Ok(core::mem::replace(&mut vec[index], value))} else {Err(err)};
+                    assert_eq!(list.try_set_at(index, value), expected);
+                },
+                2 => {
+                    let expected = if index <= len {vec.insert(index, value); Ok(())} else {Err(err)};
+                    assert_eq!(list.try_insert_at(index, value), expected);
+                },
+                3 => {
+                    let expected = if index < len {Ok(vec.remove(index))} else {Err(err)};
+                    assert_eq!(list.try_remove_at(index), expected);
+                },
+                _ => {
+                    assert_eq!(list.try_get(index), vec.get(index).ok_or(err));
+                    match list.try_get_mut(index) {
+                        Ok(mut element) => {
+                            assert_eq!(element.index(), index);
+                            *element = value;
+                            vec[index] = value;
+                        },
+                        Err(e) => assert_eq!(e, err),
+                    }
+                },
+            }
+            assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec);
+            assert_eq!(list.fold(), vec.iter().sum());
+        }
+        assert!((&list).debug_check_structural_integrity());
+
+        //a view reports its own length
+        let start = rng.random_range(0..=vec.len());
+        let mut view = list.mut_view_drop_left(start);
+        let view_len = vec.len() - start;
+        assert_eq!(view.try_remove_at(view_len), Err(IndexOutOfBounds { index: view_len, len: view_len }));
+        assert_eq!(view.try_insert_at(view_len + 1, 0), Err(IndexOutOfBounds { index: view_len + 1, len: view_len }));
+        assert_eq!(IndexOutOfBounds { index: 4, len: 3 }.to_string(), "Index out of bounds: the index is 4 but the length is 3");
+    }
+}
+
+#[test]
+fn test_find() {
+    let mut rng = StdRng::seed_from_u64(8008);
+    for _ in 0..200 {
+        let len = rng.random_range(0..50);
+        let vec: Vec<usize> = (0..len).map(|_| rng.random_range(0..10)).collect();
+        let list = FoldList::from_iter(|a,b| a+b, |x: &usize| *x, || 0, vec.iter().copied());
+        let threshold = rng.random_range(0..=vec.iter().sum::<usize>() + 1);
+        let expected_find_left = |vec: &[usize]| (0..vec.len())
+            .find(|i| vec[..=*i].iter().sum::<usize>() >= threshold)
+            .map(|i| (i, vec[i], vec[..i].iter().sum::<usize>()));
+        let expected_find_right = |vec: &[usize]| (0..vec.len()).rev()
+            .find(|i| vec[*i..].iter().sum::<usize>() >= threshold)
+            .map(|i| (i, vec[i], vec[i+1..].iter().sum::<usize>()));
+        let deref = |found: Option<(usize,&usize,usize)>| found.map(|(i,x,d)| (i,*x,d));
+
+        assert_eq!(deref(list.find_left(|sum| *sum >= threshold)), expected_find_left(&vec));
+        assert_eq!(deref(list.find_right(|sum| *sum >= threshold)), expected_find_right(&vec));
+        if let Some((index,_,fold)) = list.find_left(|sum| *sum >= threshold) {
+            let view = list.view_take_left_until(|sum| *sum >= threshold);
+            assert_eq!((view.len(), view.fold()), (index, fold));
+        }
+
+        let start = rng.random_range(0..=len);
+        let end = rng.random_range(start..=len);
+        let view = list.view_range(start..end);
+        assert_eq!(deref(view.find_left(|sum| *sum >= threshold)), expected_find_left(&vec[start..end]));
+        assert_eq!(deref(view.find_right(|sum| *sum >= threshold)), expected_find_right(&vec[start..end]));
+
+        let reversed: Vec<usize> = vec[start..end].iter().rev().copied().collect();
+        let view = list.view_range(start..end).view_reversed();
+        assert_eq!(deref(view.find_left(|sum| *sum >= threshold)), expected_find_left(&reversed));
+        assert_eq!(deref(view.find_right(|sum| *sum >= threshold)), expected_find_right(&reversed));
+    }
+}
+
+#[test]
+fn test_cached_delta() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    static DELTA_OF_CALLS: AtomicUsize = AtomicUsize::new(0);
+    let mut rng = StdRng::seed_from_u64(9009);
+    let inner = FoldList::new(|a: String, b: String| a + &b, |x: &usize| {DELTA_OF_CALLS.fetch_add(1, Ordering::Relaxed); x.to_string()}, String::new).get_settings();
+    let settings = CachedDeltaSettings(inner);
+    let mut list = FoldList::from_settings(settings);
+    let mut vec: Vec<usize> = vec![];
+    let mut expected_calls = 0;
+    for _ in 0..500 {
+        let index = rng.random_range(0..=vec.len());
+        let value = rng.random_range(0..100);
+        match rng.random_range(0..4) {
+            0 | 1 => {
+                list.insert_at(index, settings.wrap(value));
+                vec.insert(index, value);
+                expected_calls += 1;
+            },
+            2 if index < vec.len() => {
+                assert_eq!(list.remove_at(index).into_value(), vec.remove(index));
+            },
+            _ if index < vec.len() => {
+                list.update_at(index, |x: &mut CachedDelta<usize,String>| x.update(&inner, |x| *x = value));
+                vec[index] = value;
+                expected_calls += 1;
+            },
+            _ => {},
+        }
+        //delta_of is only called once per element created or changed, regardless of rebalancing
+        assert_eq!(DELTA_OF_CALLS.load(Ordering::Relaxed), expected_calls);
+        assert_eq!(list.fold(), vec.iter().map(|x| x.to_string()).collect::<String>());
+    }
+    assert_eq!(list.iter().map(|x| x.delta().clone()).collect::<Vec<_>>(), vec.iter().map(|x| x.to_string()).collect::<Vec<_>>());
+    assert!((&list).debug_check_structural_integrity());
+
+    //a closure panicking partway through an update still leaves the cached delta right
+    let mut cached = CachedDelta::new(5, &inner);
+    assert!(catch_unwind(AssertUnwindSafe(|| cached.update(&inner, |x| { *x = 7; panic!("update failed") }))).is_err());
+    assert_eq!((cached.value(), cached.delta().as_str()), (&7, "7"));
+}
+
+#[test]
+fn test_monoids() {
+    let mut rng = StdRng::seed_from_u64(10010);
+    for _ in 0..50 {
+        let len = rng.random_range(0..40);
+        let ints: Vec<u64> = (0..len).map(|_| rng.random_range(0..20)).collect();
+        let bools: Vec<bool> = (0..len).map(|_| rng.random_bool(0.8)).collect();
+        let floats: Vec<f64> = (0..len).map(|_| rng.random_range(0..4) as f64).collect();
+        check_monoid(&mut rng, monoids::Sum, &ints, |r| r.iter().sum());
+        check_monoid(&mut rng, monoids::Product, &floats, |r| r.iter().product());
+        check_monoid(&mut rng, monoids::Min, &ints, |r| r.iter().min().copied());
+        check_monoid(&mut rng, monoids::Max, &ints, |r| r.iter().max().copied());
+        check_monoid(&mut rng, monoids::MinMax, &ints, |r| r.iter().min().copied().zip(r.iter().max().copied()));
+        check_monoid(&mut rng, monoids::ArgMin, &ints, |r| (r.len(), r.iter().copied().enumerate().min_by_key(|(_,x)| *x).map(|(i,x)| (x,i))));
+        check_monoid(&mut rng, monoids::ArgMax, &ints, |r| (r.len(), r.iter().copied().enumerate().max_by_key(|(i,x)| (*x, core::cmp::Reverse(*i))).map(|(i,x)| (x,i))));
+        check_monoid(&mut rng, monoids::Min, &floats, |r| r.iter().copied().reduce(f64::min));
+        check_monoid(&mut rng, monoids::MinMax, &floats, |r| r.iter().copied().reduce(f64::min).zip(r.iter().copied().reduce(f64::max)));
+        check_monoid(&mut rng, monoids::ArgMin, &floats, |r| (r.len(), r.iter().copied().enumerate().reduce(|a,b| if b.1 < a.1 {b} else {a}).map(|(i,x)| (x,i))));
+        check_monoid(&mut rng, monoids::Count, &ints, |r| r.len());
+        check_monoid(&mut rng, monoids::Xor, &ints, |r| r.iter().fold(0, |a,b| a ^ b));
+        check_monoid(&mut rng, monoids::Gcd, &ints, |r| r.iter().fold(0, |mut a, b| {let mut b = *b; while b != 0 {(a,b) = (b, a % b)} a}));
+        check_monoid(&mut rng, monoids::Any, &bools, |r| r.iter().any(|x| *x));
+        check_monoid(&mut rng, monoids::All, &bools, |r| r.iter().all(|x| *x));
+        check_monoid(&mut rng, monoids::First, &ints, |r| r.first().copied());
+        check_monoid(&mut rng, monoids::Last, &ints, |r| r.last().copied());
+    }
+}
+
+fn check_monoid<T: Clone + Debug, D: Clone + PartialEq + Debug, S: FoldSettings<T,D>>(rng: &mut impl Rng, settings: S, vec: &[T], expected_fold: impl Fn(&[T]) -> D) {
+    let mut list = FoldList::from_settings(settings);
+    list.append_right_from_iter(vec.iter().cloned());
+    assert_eq!(list.fold(), expected_fold(vec));
+    let start = rng.random_range(0..=vec.len());
+    let end = rng.random_range(start..=vec.len());
+    assert_eq!(list.view_range(start..end).fold(), expected_fold(&vec[start..end]));
+}
+
+#[test]
+fn test_laws() {
+    let samples: Vec<i64> = vec![3, -7, 0, 12, 5, -1];
+    assert_eq!(laws::check_settings(monoids::Sum, &samples), Ok(()));
+    assert_eq!(laws::check_settings(monoids::MinMax, &samples), Ok(()));
+    assert_eq!(laws::check_settings(monoids::ArgMax, &samples), Ok(()));
+    assert_eq!(laws::check_settings(monoids::Gcd, &[12u32, 18, 7, 0]), Ok(()));
+
+    let subtract = FoldList::new(|a: i64, b: i64| a - b, |x: &i64| *x, || 0).get_settings();
+    assert_eq!(laws::check_settings(subtract, &samples), Err(SettingsLawViolation::NotLeftIdentity { a: 3, result: -3 }));
+
+    let not_associative = FoldList::new(|a: i64, b: i64| if a == 0 {b} else if b == 0 {a} else {2*a + b}, |x: &i64| *x, || 0).get_settings();
+    let violation = laws::check_settings(not_associative, &samples).unwrap_err();
+    let SettingsLawViolation::NotAssociative { a, b, c, left_first, right_first } = violation else {panic!("{}", violation)};
+    assert_eq!(not_associative.op(not_associative.op(a, b), c), left_first);
+    assert_eq!(not_associative.op(a, not_associative.op(b, c)), right_first);
+    assert_ne!(left_first, right_first);
+    assert!(violation.to_string().starts_with("op is not associative"));
+
+    let sum = FoldList::new(|a: i64, b: i64| a + b, |x: &i64| *x, || 0).get_settings();
+    let last_digit = SimplificationWithoutShortcut { simplifier: |d: &i64| d.rem_euclid(10), op2: |(a,b): (i64,i64)| (a + b) % 10, _m: PhantomData };
+    assert_eq!(laws::check_simplification(last_digit, sum, &samples), Ok(()));
+    let wrong_op = SimplificationWithoutShortcut { simplifier: |d: &i64| d.rem_euclid(10), op2: |(a,b): (i64,i64)| (a * b) % 10, _m: PhantomData };
+    let violation = laws::check_simplification(wrong_op, sum, &samples).unwrap_err();
+    let SimplificationLawViolation::DoesNotCommuteWithOp { a, b, simplified_op, op_of_simplified } = violation else {panic!("{}", violation)};
+    assert_eq!((a + b).rem_euclid(10), simplified_op);
+    assert_eq!((a.rem_euclid(10) * b.rem_euclid(10)) % 10, op_of_simplified);
+    let wrong_shortcut = SimplificationWithShortcut { simplifier: |d: &i64| d.rem_euclid(10), op2: |(a,b): (i64,i64)| (a + b) % 10, empty_shortcut: |()| 0, delta_shortcut: |x: &i64| x % 10, _m: PhantomData };
+    assert_eq!(laws::check_simplification(wrong_shortcut, sum, &samples), Err(SimplificationLawViolation::DeltaMismatch { index: 1, simplified_delta: -7, delta_simplified: 3 }));
+}
+
+#[test]
+fn test_comparisons() {
+    use std::{collections::hash_map::DefaultHasher, hash::{Hash, Hasher}};
+    fn hash_of(x: &impl Hash) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        x.hash(&mut hasher);
+        hasher.finish()
+    }
+    let mut rng = StdRng::seed_from_u64(12012);
+    let mut vecs: Vec<Vec<u8>> = (0..40).map(|_| (0..rng.random_range(0..6)).map(|_| rng.random_range(0..3)).collect()).collect();
+    let mut lists: Vec<_> = vecs.iter().map(|v| FoldList::from_iter(|a,b| a+b, |x: &u8| *x as usize, || 0, v.iter().copied())).collect();
+    for (vec, list) in vecs.iter().zip(&lists) {
+        //the same elements, inserted in a different order so that the tree has a different shape, and with different settings
+        let mut other = FoldList::new(|a: Option<u8>, b: Option<u8>| a.max(b), |x: &u8| Some(*x), || None);
+        for x in vec.iter().rev() {
+            other.append_left(*x);
+        }
+        assert!(*list == other);
+        assert_eq!(*list, *vec);
+        assert_eq!(*list, vec[..]);
+        assert_eq!(hash_of(list), hash_of(&other));
+        let reversed: Vec<u8> = vec.iter().rev().copied().collect();
+        assert_eq!(list.view_reversed(), reversed);
+        assert_eq!(list.as_sized_chain().view_reversed(), reversed);
+        assert_eq!(list.view_reversed(), other.view_reversed());
+        assert_eq!(hash_of(&list.view_reversed()), hash_of(&other.view_reversed()));
+        let chain = FoldChain::from_iter(|a,b| a+b, |x: &u8| *x as usize, || 0, vec.iter().copied());
+        assert_eq!(chain, *vec);
+        assert_eq!(hash_of(&chain), hash_of(&FoldChain::from_iter(|a,b| a+b, |x: &u8| *x as usize, || 0, vec.iter().copied())));
+        assert_eq!(hash_of(&chain), hash_of(&(&chain).as_imm()));
+    }
+    for (i, j) in (0..vecs.len()).zip((0..vecs.len()).rev()) {
+        assert_eq!(lists[i] == lists[j], vecs[i] == vecs[j]);
+        assert_eq!(lists[i].cmp(&lists[j]), vecs[i].cmp(&vecs[j]));
+        assert_eq!(lists[i].view_reversed().partial_cmp(&lists[j].view_reversed()), vecs[i].iter().rev().partial_cmp(vecs[j].iter().rev()));
+    }
+    vecs.sort();
+    lists.sort();
+    assert!(lists.iter().zip(&vecs).all(|(list, vec)| *list == *vec));
+}
+
+#[test]
+fn test_collect_and_extend() {
+    let mut list: FoldList<u32,u32,monoids::Sum> = (1..=4).collect();
+    assert_eq!(list, [1,2,3,4][..]);
+    assert_eq!(list.fold(), 10);
+    list.extend([5,6]);
+    assert_eq!(list, [1,2,3,4,5,6][..]);
+    assert_eq!(list.fold(), 21);
+    list.mut_view_range(1..3).extend([10,20]);
+    assert_eq!(list, [1,2,3,10,20,4,5,6][..]);
+    assert_eq!(list.fold(), 51);
+    list.mut_view_reversed().extend([0]);
+    assert_eq!(list, [0,1,2,3,10,20,4,5,6][..]);
+    assert_eq!(FoldList::<u32,u32,monoids::Sum>::default().fold(), 0);
+
+    let mut chain: FoldChain<u32,Option<u32>,monoids::Max> = [3,1,4].into_iter().collect();
+    assert_eq!(chain.fold(), Some(4));
+    chain.extend([1,5]);
+    assert_eq!(chain, [3,1,4,1,5][..]);
+    assert_eq!(chain.fold(), Some(5));
+    chain.mut_view_reversed().extend([9]);
+    assert_eq!(chain, [9,3,1,4,1,5][..]);
+    assert_eq!(chain.fold(), Some(9));
+    assert_eq!(FoldChain::<u32,Option<u32>,monoids::Max>::default().fold(), None);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    use foldlist::serde::{FoldChainSeed, FoldListSeed};
+    use serde::de::{DeserializeSeed, value::{Error, SeqDeserializer}};
+    use serde_test::{assert_tokens, assert_de_tokens_error, Token};
+    let list: FoldList<u32,u32,monoids::Sum> = [3,1,4].into_iter().collect();
+    let tokens = [Token::Seq { len: Some(3) }, Token::U32(3), Token::U32(1), Token::U32(4), Token::SeqEnd];
+    assert_tokens(&list, &tokens);
+    let chain: FoldChain<u32,Option<u32>,monoids::Max> = [3,1,4].into_iter().collect();
+    assert_tokens(&chain, &tokens);
+    assert_tokens(&FoldList::<u32,u32,monoids::Sum>::default(), &[Token::Seq { len: Some(0) }, Token::SeqEnd]);
+    assert_de_tokens_error::<FoldList<u32,u32,monoids::Sum>>(&[Token::Seq { len: Some(2) }, Token::U32(3), Token::Str("x")], "invalid type: string \"x\", expected u32");
+
+    let long: Vec<u32> = (0..1000).collect();
+    let loaded: FoldList<u32,u32,monoids::Sum> = serde::Deserialize::deserialize(SeqDeserializer::<_,Error>::new(long.iter().copied())).unwrap();
+    assert_eq!(loaded, long);
+    assert_eq!(loaded.fold(), 499500);
+    assert_eq!(loaded.view_drop_left(10).view_take_left(10).fold(), 145);
+
+    let template = FoldList::new(|a: usize, b: usize| a + b, |s: &String| s.len(), || 0);
+    let strings = FoldListSeed::new(template.get_settings()).deserialize(SeqDeserializer::<_,Error>::new(["ab","","cde"].into_iter())).unwrap();
+    assert_eq!(strings, ["ab","","cde"][..]);
+    assert_eq!(strings.fold(), 5);
+    let template = FoldChain::new(|a: usize, b: usize| a.max(b), |s: &String| s.len(), || 0);
+    let strings = FoldChainSeed::new(template.get_settings()).deserialize(SeqDeserializer::<_,Error>::new(["ab","","cde"].into_iter())).unwrap();
+    assert_eq!(strings, ["ab","","cde"][..]);
+    assert_eq!(strings.fold(), 3);
+    assert!(FoldChainSeed::new(template.get_settings()).deserialize(SeqDeserializer::<_,Error>::new([1,2].into_iter())).is_err());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_rayon() {
+    use rayon::prelude::*;
+    let mut rng = StdRng::seed_from_u64(15015);
+    for len in [0, 1, 2, 7, 1000, 20000] {
+        let vec: Vec<u64> = (0..len).map(|_| rng.random_range(0..1000)).collect();
+        let mut list = FoldList::par_from_vec(monoids::Sum, vec.clone());
+        assert_eq!(list, vec);
+        assert_eq!(list.fold(), vec.iter().sum::<u64>());
+        let chain = FoldChain::par_from_vec(monoids::Max, vec.clone());
+        assert_eq!(chain, vec);
+        assert_eq!(chain.fold(), vec.iter().max().copied());
+
+        assert_eq!(list.par_iter().copied().collect::<Vec<_>>(), vec);
+        assert_eq!(list.par_iter().rev().copied().collect::<Vec<_>>(), vec.iter().rev().copied().collect::<Vec<_>>());
+        assert_eq!(list.par_iter().len(), len);
+        assert_eq!(list.par_iter().map(|x| *x).sum::<u64>(), vec.iter().sum::<u64>());
+        if len >= 2 {
+            let (a, b) = (rng.random_range(0..len), rng.random_range(0..len));
+            let (a, b) = (a.min(b), a.max(b));
+            assert_eq!(list.view_range(a..b).into_par_iter().copied().collect::<Vec<_>>(), vec[a..b]);
+            assert_eq!(list.view_drop_left(a).into_par_iter().with_min_len(1).copied().collect::<Vec<_>>(), vec[a..]);
+            let reversed: Vec<u64> = vec[a..b].iter().rev().copied().collect();
+            assert_eq!(list.view_range(a..b).view_reversed().into_par_iter().with_max_len(3).copied().collect::<Vec<_>>(), reversed);
+            assert_eq!(list.view_reversed().view_range(len - b..len - a).into_par_iter().copied().collect::<Vec<_>>(), reversed);
+            let zipped: Vec<(u64,u64)> = list.view_reversed().into_par_iter().zip(list.par_iter()).map(|(x,y)| (*x,*y)).collect();
+            assert_eq!(zipped, vec.iter().rev().copied().zip(vec.iter().copied()).collect::<Vec<_>>());
+        }
+
+        list.par_foreach_mut(|x| *x = *x * 2 + 1);
+        let mapped: Vec<u64> = vec.iter().map(|x| x * 2 + 1).collect();
+        assert_eq!(list, mapped);
+        assert_eq!(list.fold(), mapped.iter().sum::<u64>());
+        if len >= 2 {
+            let (a, b) = (rng.random_range(0..len), rng.random_range(0..len));
+            let (a, b) = (a.min(b), a.max(b));
+            assert_eq!(list.view_range(a..b).fold(), mapped[a..b].iter().sum::<u64>());
+        }
+        list.append_right(5);
+        list.remove_at(0);
+        assert_eq!(list.fold(), mapped.iter().sum::<u64>() + 5 - mapped.first().copied().unwrap_or(5));
+    }
+
+    //the folds stay right even if f panics partway through
+    let mut list = FoldList::par_from_vec(monoids::Sum, (0..20000u64).collect());
+    assert!(catch_unwind(AssertUnwindSafe(|| list.par_foreach_mut(|x| { *x += 1; if *x == 12345 { panic!("update failed") } }))).is_err());
+    assert_eq!(list.fold(), list.iter().sum::<u64>());
+    assert_eq!(list.view_range(1000..15000).fold(), list.iter().skip(1000).take(14000).sum::<u64>());
+    assert!((&list).debug_check_structural_integrity());
+}
+
+#[test]
+fn test_node_pool() {
+    let mut list = FoldList::from_settings_and_pool(monoids::Sum, NodePool::with_capacity(5));
+    assert_eq!((list.len(), list.capacity()), (0, 5));
+    list.extend(0..3u32);
+    assert_eq!(list.node_pool().capacity(), 2);
+    list.insert_at(1, 10);
+    assert_eq!(list.node_pool().capacity(), 1);
+    assert_eq!(list.remove_at(0), 0);
+    assert_eq!(list.pop_right(), Some(2));
+    assert_eq!((list.node_pool().capacity(), list.capacity()), (3, 5));
+    assert_eq!(list, [10, 1][..]);
+    assert_eq!(list.fold(), 11);
+    list.cursor_front_mut().remove_current();
+    assert_eq!(list.node_pool().capacity(), 4);
+    list.append_left(7);
+    list.append_right(8);
+    assert_eq!(list.node_pool().capacity(), 2);
+    let pool = list.into_node_pool();
+    assert_eq!(pool.capacity(), 5);
+    let mut list = FoldList::from_settings_and_pool(monoids::Sum, pool);
+    list.extend(0..7u32);
+    assert_eq!((list.node_pool().capacity(), list.capacity()), (0, 7));
+    assert_eq!(list.fold(), 21);
+    list.mut_view_range(1..4).take_all();
+    assert_eq!(list.node_pool().capacity(), 0);
+    assert_eq!(list.mut_view_range(..).drain().count(), 4);
+    assert_eq!(list.node_pool().capacity(), 4);
+    list.shrink_to_fit();
+    assert_eq!(list.capacity(), list.len());
+
+    //a list without a pool only uses allocations which were made ahead of time
+    let mut chain = FoldChain::from_settings(monoids::Max);
+    chain.append_right(3u32);
+    chain.pop_right();
+    assert_eq!(chain.node_pool().capacity(), 0);
+    chain.node_pool_mut().ensure_capacity(3);
+    chain.append_right_from_iter([1, 4].into_iter());
+    assert_eq!(chain.node_pool().capacity(), 1);
+    chain.pop_left();
+    assert_eq!(chain.node_pool().capacity(), 1);
+    assert_eq!(chain.fold(), Some(4));
+    let mut chain = FoldChain::from_settings_and_pool(monoids::Max, chain.into_node_pool());
+    assert_eq!(chain.node_pool().capacity(), 2);
+    chain.append_left(5);
+    assert_eq!((chain.node_pool().capacity(), chain.fold()), (1, Some(5)));
+
+    //lists split off from, taken from or cloned from a pooled list keep removed allocations too, and the spares stay behind
+    let mut list = FoldList::from_settings_and_pool(monoids::Sum, NodePool::with_capacity(2));
+    list.append_right_from_iter(0..10u32);
+    let mut split = list.split_off(5);
+    let mut taken = list.take_all();
+    let mut cloned = taken.clone();
+    assert_eq!((list.node_pool().capacity(), split.node_pool().capacity()), (0, 0));
+    for l in [&mut split, &mut taken, &mut cloned] {
+        l.pop_left();
+        assert_eq!(l.node_pool().capacity(), 1);
+    }
+    list.append_right(1);
+    list.pop_right();
+    assert_eq!(list.node_pool().capacity(), 1);
+
+    //compare against a vec while reusing allocations
+    let mut rng = StdRng::seed_from_u64(17017);
+    let mut pool = NodePool::new();
+    for _ in 0..20 {
+        let mut list = FoldList::from_settings_and_pool(monoids::Sum, pool);
+        let mut vec: Vec<u64> = vec![];
+        for _ in 0..200 {
+            if rng.random_bool(0.6) || vec.is_empty() {
+                let (i, x) = (rng.random_range(0..=vec.len()), rng.random_range(0..100));
+                list.insert_at(i, x);
+                vec.insert(i, x);
+            } else {
+                let i = rng.random_range(0..vec.len());
+                assert_eq!(list.remove_at(i), vec.remove(i));
+            }
+            assert_eq!(list.fold(), vec.iter().sum::<u64>());
+        }
+        assert_eq!(list, vec);
+        assert!(list.capacity() >= vec.len());
+        pool = list.into_node_pool();
+    }
+}
+
+#[test]
+fn test_chunked() {
+    let mut list: ChunkedFoldList<u64, u64, monoids::Sum, 4> = (0..10).collect();
+    assert_eq!(list.chunk_count(), 3);
+    assert_eq!((list.len(), list.fold()), (10, 45));
+    assert_eq!(list.fold_range(3..9), 33);
+    assert_eq!((list.left(), list.right(), list[6]), (Some(&0), Some(&9), 6));
+    assert_eq!(list.find_left(|s| *s > 10), Some((5, &5, 10)));
+    assert_eq!(list.find_right(|s| *s > 10), Some((8, &8, 9)));
+    assert_eq!(list.try_get(10), Err(IndexOutOfBounds { index: 10, len: 10 }));
+    let right = list.split_off_where(|s| *s >= 6);
+    assert_eq!((list.iter().copied().collect::<Vec<_>>(), right.iter().copied().collect::<Vec<_>>()), (vec![0, 1, 2], (3..10).collect()));
+
+    //views land on element boundaries inside chunks, which are [3] [4,5,6,7] [8,9] here
+    let mut list = right;
+    let view = list.view_range(2..6);
+    assert_eq!((view.len(), view.fold(), view.left(), view.right()), (4, 26, Some(&5), Some(&8)));
+    assert_eq!(view.iter().copied().collect::<Vec<_>>(), vec![5, 6, 7, 8]);
+    assert_eq!(view.find_left(|s| *s > 11), Some((2, &7, 11)));
+    assert_eq!(view.find_right(|s| *s > 8), Some((2, &7, 8)));
+    assert_eq!(view.view_take_left_until(|s| *s > 11).iter().copied().collect::<Vec<_>>(), vec![5, 6]);
+    assert_eq!(view.view_drop_right_until(|s| *s > 8).iter().copied().collect::<Vec<_>>(), vec![5, 6, 7]);
+    assert_eq!(view.view_drop_left(1).view_take_right(2).fold(), 15);
+    assert_eq!(view.try_get(4), Err(IndexOutOfBounds { index: 4, len: 4 }));
+    #[allow(clippy::reversed_empty_ranges)]
+    let reversed = 3..2;
+    assert!(view.try_view_range(reversed).is_none() && view.try_view_range(0..5).is_none());
+    assert_eq!((list.view_drop_left_until(|s| *s > 11).left(), list.view_drop_left_until(|s| *s > 100).len()), (Some(&5), 0));
+    //extending fills the rightmost chunk before starting new ones
+    list.extend([10, 11, 12, 13]);
+    assert_eq!(list.chunks().map(|chunk| chunk.len()).collect::<Vec<_>>(), vec![1, 4, 4, 2]);
+    assert_eq!((list.chunk_list().len(), list.chunk_list().view_drop_left(1).fold().0), (4, 10));
+    //a closure panicking partway through an update leaves the chunk's delta and the folds right
+    assert!(catch_unwind(AssertUnwindSafe(|| list.update_at(1, |x| { *x = 40; panic!("update failed") }))).is_err());
+    assert_eq!((list.fold(), list.fold_range(1..2), list.chunks().nth(1).map(|chunk| *chunk.delta())), (124, 40, Some(58)));
+    assert_eq!(list.try_update_at(usize::MAX, |x| *x), Err(IndexOutOfBounds { index: usize::MAX, len: 11 }));
+
+    //compare against a vec, with small chunks so that they're split and merged often
+    let mut rng = StdRng::seed_from_u64(18018);
+    let mut list: ChunkedFoldList<u64, u64, monoids::Sum, 3> = ChunkedFoldList::default();
+    let mut vec: Vec<u64> = vec![];
+    for _ in 0..2000 {
+        match rng.random_range(0..6) {
+            0 | 1 => {
+                let (i, x) = (rng.random_range(0..=vec.len()), rng.random_range(0..100));
+                list.insert_at(i, x);
+                vec.insert(i, x);
+            },
+            2 if !vec.is_empty() => {
+                let i = rng.random_range(0..vec.len());
+                assert_eq!(list.remove_at(i), vec.remove(i));
+            },
+            3 if !vec.is_empty() => {
+                let (i, x) = (rng.random_range(0..vec.len()), rng.random_range(0..100));
+                assert_eq!(list.set_at(i, x), std::mem::replace(&mut vec[i], x));
+            },
+            4 => {
+                let x = rng.random_range(0..100);
+                if rng.random_bool(0.5) {
+                    list.append_left(x);
+                    vec.insert(0, x);
+                } else {
+                    assert_eq!(list.pop_right(), vec.pop());
+                }
+            },
+            _ => {
+                let i = rng.random_range(0..=vec.len());
+                let mut right = list.split_off(i);
+                assert_eq!(right.len(), vec.len() - i);
+                right.append_all_left(list);
+                list = right;
+            },
+        }
+        assert_eq!(list.len(), vec.len());
+        assert_eq!(list.fold(), vec.iter().sum::<u64>());
+        let (a, b) = (rng.random_range(0..=vec.len()), rng.random_range(0..=vec.len()));
+        let (a, b) = (a.min(b), a.max(b));
+        assert_eq!(list.fold_range(a..b), vec[a..b].iter().sum::<u64>());
+        let threshold = rng.random_range(0..=vec.iter().sum::<u64>() + 1);
+        let expected = vec.iter().scan(0, |s, x| {*s += x; Some(*s)}).position(|s| s > threshold);
+        assert_eq!(list.find_left(|s| *s > threshold).map(|(i, _, _)| i), expected);
+        let view = list.view_range(a..b);
+        assert!(view.iter().eq(vec[a..b].iter()));
+        let expected = vec[a..b].iter().rev().scan(0, |s, x| {*s += x; Some(*s)}).position(|s| s > threshold).map(|i| b - a - 1 - i);
+        assert_eq!(view.find_right(|s| *s > threshold).map(|(i, _, _)| i), expected);
+    }
+    assert!(list.iter().eq(vec.iter()));
+    assert!(list.chunks().all(|chunk| !chunk.is_empty()));
+}
+
+#[test]
+fn test_rope() {
+    let mut rope = Rope::from("hello\nwörld\n");
+    assert_eq!((rope.len_bytes(), rope.len_chars(), rope.len_lines()), (13, 12, 3));
+    assert_eq!(rope.line(1), "wörld\n");
+    assert_eq!(rope.line(2), "");
+    assert_eq!(rope.byte_to_line_col(10), (1, 3));
+    assert_eq!(rope.line_col_to_byte(1, 3), 10);
+    assert_eq!((rope.char_to_byte(8), rope.byte_to_char(9), rope.char(7)), (9, 8, 'ö'));
+    rope.insert_str(6, "big ");
+    rope.remove(0..6);
+    assert_eq!(rope, "big wörld\n");
+    assert_eq!(rope.to_string(), "big wörld\n");
+    assert_eq!(rope.line_col_to_byte(0, 9), 10);
+
+    //a remove across chunks which leaves a small piece next to a full chunk rebalances them
+    let text = "a".repeat(512) + &"b".repeat(512) + &"c".repeat(512);
+    let mut rope = Rope::from(text.as_str());
+    rope.remove(500..1530);
+    assert_eq!(rope.chunks().map(str::len).collect::<Vec<_>>(), vec![506]);
+    let mut rope = Rope::from(text.as_str());
+    rope.remove(1030..1530);
+    assert_eq!(rope.chunks().map(str::len).collect::<Vec<_>>(), vec![512, 262, 262]);
+
+    //compare against a string, with enough text that chunks are split and merged
+    let mut rng = StdRng::seed_from_u64(19019);
+    let mut rope = Rope::new();
+    let mut string = String::new();
+    let boundary = |string: &String, rng: &mut StdRng| {
+        let mut i = rng.random_range(0..=string.len());
+        while !string.is_char_boundary(i) {
+            i -= 1;
+        }
+        i
+    };
+    for _ in 0..400 {
+        if rng.random_bool(0.6) || string.is_empty() {
+            let text: String = (0..rng.random_range(0..300)).map(|_| match rng.random_range(0..10) {
+                0 => '\n',
+                1 => 'é',
+                2 => '😀',
+                _ => rand_char(&mut rng),
+            }).collect();
+            let i = boundary(&string, &mut rng);
+            rope.insert_str(i, &text);
+            string.insert_str(i, &text);
+        } else {
+            let (a, b) = (boundary(&string, &mut rng), boundary(&string, &mut rng));
+            let (a, b) = (a.min(b), a.max(b));
+            rope.remove(a..b);
+            string.replace_range(a..b, "");
+        }
+        assert_eq!(rope, string.as_str());
+        assert_eq!((rope.len_bytes(), rope.len_chars(), rope.len_lines()), (string.len(), string.chars().count(), string.lines().count() + usize::from(string.is_empty() || string.ends_with('\n'))));
+        assert!(rope.chunks().all(|chunk| !chunk.is_empty() && chunk.len() <= 512));
+        assert!(rope.chunks().count() == 1 || rope.chunks().all(|chunk| chunk.len() >= 256 - 3));
+        let i = boundary(&string, &mut rng);
+        let line = string[..i].matches('\n').count();
+        let line_start = string[..i].rfind('\n').map_or(0, |n| n + 1);
+        let col = string[line_start..i].chars().count();
+        assert_eq!(rope.byte_to_line_col(i), (line, col));
+        assert_eq!(rope.line_col_to_byte(line, col), i);
+        assert_eq!(rope.line_to_byte(line), line_start);
+        assert_eq!(rope.char_to_byte(rope.byte_to_char(i)), i);
+        assert_eq!(rope.line(line), string[line_start..].split_inclusive('\n').next().unwrap_or(""));
+        assert_eq!(rope.slice(line_start..i), &string[line_start..i]);
+    }
+}
+
+#[test]
+#[should_panic(expected = "Index out of bounds: the column is 6 but the length of line 1 is 5")]
+fn test_rope_column_out_of_bounds() {
+    Rope::from("hello\nwörld\n").line_col_to_byte(1, 6);
+}
+
+#[test]
+#[should_panic(expected = "Invalid range: the range starts at 4 but ends at 2")]
+fn test_rope_remove_reversed() {
+    #[allow(clippy::reversed_empty_ranges)]
+    Rope::from("hello\nwörld\n").remove(4..2);
+}
+
+#[test]
+#[should_panic(expected = "Index out of bounds: the line is 18446744073709551615 but the number of lines is 3")]
+fn test_rope_line_usize_max() {
+    Rope::from("hello\nwörld\n").line_col_to_byte(usize::MAX, 0);
+}
+
+#[test]
+fn test_sorted_fold_list() {
+    let mut list = SortedFoldList::new(|a: u64, b: u64| a.max(b), |t: &u64| *t, || 0);
+    for (k, t) in [(5, 50), (1, 10), (3, 30), (3, 31), (9, 90)] {
+        list.insert(k, t);
+    }
+    assert_eq!(list.iter().map(|(_, t)| *t).collect::<Vec<_>>(), vec![10, 30, 31, 50, 90]);
+    assert_eq!((list.rank(&3), list.rank(&4), list.count(&3)), (1, 3, 2));
+    assert_eq!(list.select(2), Some((&3, &31)));
+    assert_eq!(list.range(2..=5).fold(), 50);
+    assert_eq!(list.range(2..5).len(), 2);
+    assert_eq!(list.fold_range(..3), 10);
+    assert_eq!(list.remove(&3), Some(30));
+    assert_eq!(list.remove(&4), None);
+    assert_eq!((list.get(&3), list.first(), list.last()), (Some(&31), Some((&1, &10)), Some((&9, &90))));
+
+    //reversed key ranges are empty, and the largest key and index don't overflow anything
+    let mut list: SortedFoldList<u32, u64, u64, monoids::Sum> = [(u32::MAX, 8), (3, 4), (1, 1), (3, 3)].into_iter().collect();
+    #[allow(clippy::reversed_empty_ranges)]
+    let reversed = (list.index_range(5..2), list.range(5..2).len(), list.fold_range(5..2), list.fold_range(3..3));
+    assert_eq!(reversed, (3..3, 0, 0, 0));
+    assert_eq!((list.rank(&u32::MAX), list.index_range(u32::MAX..), list.fold_range(..=u32::MAX), list.fold_range(2..u32::MAX)), (3, 3..4, 16, 7));
+    assert_eq!((list.select(usize::MAX), list.select(3), list.count(&u32::MAX)), (None, Some((&u32::MAX, &8)), 1));
+    //a closure panicking partway through an update leaves the folds right
+    assert!(catch_unwind(AssertUnwindSafe(|| list.update_at(2, |t| { *t = 40; panic!("update failed") }))).is_err());
+    assert_eq!((list.fold_range(3..=3), list.fold()), (44, 53));
+    assert_eq!((list.pop_last(), list.pop_first(), list.len()), (Some((u32::MAX, 8)), Some((1, 1)), 2));
+}
+
+#[test]
+fn test_fold_map() {
+    let mut map = FoldMap::new(|a: u64, b: u64| a.max(b), |v: &u64| *v, || 0);
+    for (k, v) in [(5, 50), (1, 10), (3, 30), (9, 90)] {
+        assert_eq!(map.insert(k, v), None);
+    }
+    assert_eq!(map.insert(3, 31), Some(30));
+    assert_eq!((map.len(), map.get(&3), map.get(&4)), (4, Some(&31), None));
+    assert_eq!(map.fold_range(2..=5), 50);
+    assert_eq!(map.fold_range(2..5), 31);
+    assert_eq!(map.fold_range(6..), 90);
+    #[allow(clippy::reversed_empty_ranges)]
+    let reversed = 5..2;
+    assert_eq!(map.fold_range(reversed), 0);
+    assert_eq!(map.range(..5).map(|(k, _)| *k).collect::<Vec<_>>(), vec![1, 3]);
+    assert_eq!(*map.entry(4).or_insert(40), 40);
+    assert_eq!(*map.entry(4).and_modify(|v| *v += 1).or_insert(0), 41);
+    assert!(matches!(map.entry(7), Entry::Vacant(_)));
+    assert_eq!(map.update(&9, |v| std::mem::replace(v, 1)), Some(90));
+    assert_eq!(map.fold(), 50);
+    {
+        let mut range = map.range_mut(3..=5);
+        assert_eq!(range.fold(), 50);
+        range.foreach_mut(|k, v| *v = *k as u64 * 100);
+        assert_eq!(range.fold(), 500);
+        assert_eq!(range.pop_last(), Some((5, 500)));
+    }
+    assert_eq!(map.len(), 4);
+    assert_eq!(map.iter().cloned().collect::<Vec<_>>(), vec![(1, 10), (3, 300), (4, 400), (9, 1)]);
+    let taken = map.range_mut(2..).take_all();
+    assert_eq!((map.len(), taken.len(), taken.fold()), (1, 3, 400));
+    assert_eq!(map.remove(&1), Some(10));
+    assert!(map.is_empty());
+
+    //the folds are still right after a closure panics partway through an update
+    let mut map: FoldMap<u32, u64, u64, monoids::Sum> = [(0, 1), (7, 2), (u32::MAX, 4)].into_iter().collect();
+    let result = catch_unwind(AssertUnwindSafe(|| map.update(&7, |v| { *v = 20; panic!("update failed") })));
+    assert!(result.is_err());
+    assert_eq!((map.fold(), map.fold_range(1..), map.get(&7)), (25, 24, Some(&20)));
+    assert!(catch_unwind(AssertUnwindSafe(|| { map.entry(u32::MAX).and_modify(|v| { *v = 40; panic!("update failed") }); })).is_err());
+    #[allow(clippy::reversed_empty_ranges)]
+    let reversed = map.fold_range(u32::MAX..0);
+    assert_eq!((map.fold(), map.fold_range(u32::MAX..), reversed), (61, 40, 0));
+
+    //compare against a BTreeMap
+    let mut rng = StdRng::seed_from_u64(20021);
+    let mut map: FoldMap<u32, u64, u64, monoids::Sum> = FoldMap::default();
+    let mut btree = std::collections::BTreeMap::new();
+    for i in 0..500 {
+        let key = rng.random_range(0..50);
+        match rng.random_range(0..4) {
+            0 | 1 => assert_eq!(map.insert(key, i), btree.insert(key, i)),
+            2 => assert_eq!(map.remove(&key), btree.remove(&key)),
+            _ => {
+                *btree.entry(key).or_insert(0) += 1;
+                map.entry(key).and_modify(|v| *v += 1).or_insert(1);
+            },
+        }
+        let (a, b) = (rng.random_range(0..50), rng.random_range(0..50));
+        assert_eq!(map.get(&a), btree.get(&a));
+        assert_eq!(map.fold_range(a..b), if a <= b { btree.range(a..b).map(|(_, v)| v).sum::<u64>() } else { 0 });
+        assert_eq!(map.range_mut(a..=b).fold(), if a <= b { btree.range(a..=b).map(|(_, v)| v).sum::<u64>() } else { 0 });
+        assert_eq!(map.len(), btree.len());
+    }
+    assert!(map.iter().map(|(k, v)| (k, v)).eq(btree.iter()));
+}
+
+#[cfg(feature = "handles")]
+#[test]
+fn test_handles() {
+    use foldlist::handle::Handle;
+    let mut list = FoldList::from_settings_and_pool(monoids::Sum, NodePool::new());
+    let a = list.append_right_with_handle(1u64);
+    let b = list.append_left_with_handle(10);
+    let c = list.insert_at_with_handle(1, 100);
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![10, 100, 1]);
+    assert_eq!((list.index_of(&a), list.index_of(&b), list.index_of(&c)), (Some(2), Some(0), Some(1)));
+    assert_eq!(list.prefix_fold_of(&a), Some(110));
+    assert_eq!(list.update_by_handle(&c, |v| std::mem::replace(v, 1000)), Some(100));
+    assert_eq!((list.fold(), list.value_of(&c)), (1011, Some(&1000)));
+    assert_eq!(list.handle_at(1), c);
+    assert_eq!(list.remove_by_handle(&c), Some(1000));
+    assert_eq!((list.index_of(&c), list.remove_by_handle(&c), list.update_by_handle(&c, |_| ())), (None, None, None));
+    //the freed node is reused, but the old handle stays invalid
+    let d = list.append_right_with_handle(5);
+    assert_eq!((list.index_of(&c), list.index_of(&d)), (None, Some(2)));
+    let taken = list.mut_view_drop_left(1).take_all();
+    assert_eq!((list.index_of(&a), taken.index_of(&a), list.index_of(&b)), (None, Some(0), Some(0)));
+    let cloned = list.clone();
+    assert_eq!(cloned.index_of(&b), None);
+
+    //a closure panicking partway through an update leaves the folds right and the handle valid
+    let mut list: FoldList<u64, u64, monoids::Sum> = (1..=4).collect();
+    let handle = list.handle_at(2);
+    assert!(catch_unwind(AssertUnwindSafe(|| list.update_by_handle(&handle, |v| { *v = 30; panic!("update failed") }))).is_err());
+    assert_eq!((list.fold(), list.prefix_fold(3), list.index_of(&handle), list.value_of(&handle)), (37, 33, Some(2), Some(&30)));
+    //handles to elements of a dropped list are just invalid
+    let dangling: Handle<u64, u64> = {
+        let mut dropped: FoldList<u64, u64, monoids::Sum> = (0..3).collect();
+        dropped.handle_at(1)
+    };
+    assert_eq!((list.index_of(&dangling), list.update_by_handle(&dangling, |v| *v), list.remove_by_handle(&dangling)), (None, None, None));
+    assert_eq!(list.fold(), 37);
+}
+
+#[cfg(feature = "handles")]
+#[test]
+fn test_handles_across_lists() {
+    let mut list: FoldList<u64, u64, monoids::Sum> = (0..4).collect();
+    let handles: Vec<_> = (0..4).map(|i| list.handle_at(i)).collect();
+    let mut other = FoldList::from_settings(monoids::Sum);
+    let x = other.append_right_with_handle(10);
+    assert_eq!((list.index_of(&x), other.index_of(&handles[0]), other.remove_by_handle(&handles[0])), (None, None, None));
+    //handles follow their elements when they're split off and appended elsewhere
+    let split = list.split_off(2);
+    assert_eq!((list.index_of(&handles[2]), split.index_of(&handles[2]), list.index_of(&handles[1])), (None, Some(0), Some(1)));
+    other.append_all_left(split);
+    assert_eq!((other.index_of(&handles[3]), other.index_of(&x)), (Some(1), Some(2)));
+    let taken = other.take_all();
+    list.append_all_right(taken);
+    assert!(handles.iter().enumerate().all(|(i, h)| list.index_of(h) == Some(i)));
+    assert_eq!((list.index_of(&x), other.index_of(&x)), (Some(4), None));
+    core::mem::swap(&mut list, &mut other);
+    assert_eq!((list.index_of(&x), other.value_of(&x)), (None, Some(&10)));
+    //whichever side of a split or merge has fewer handles is the one whose handles are pointed to a new list
+    let mut head: FoldList<u64, u64, monoids::Sum> = (0..10).collect();
+    let ten: Vec<_> = (0..10).map(|i| head.handle_at(i)).collect();
+    let mut tail = head.split_off(1);
+    assert!((1..10).all(|i| tail.index_of(&ten[i]) == Some(i - 1) && head.index_of(&ten[i]).is_none()));
+    assert_eq!((head.index_of(&ten[0]), tail.index_of(&ten[0])), (Some(0), None));
+    assert_eq!((head.remove_by_handle(&ten[0]), tail.split_off(9).len()), (Some(0), 0));
+    head.append_all_right(tail);
+    assert!((1..10).all(|i| head.index_of(&ten[i]) == Some(i - 1)));
+    assert!((&head).debug_check_structural_integrity());
+
+    //many splits and merges, so that the lists' identities are retired and reused
+    let mut rng = StdRng::seed_from_u64(22022);
+    for _ in 0..300 {
+        let index = rng.random_range(0..=other.len());
+        let mut right = other.split_off(index);
+        if rng.random_bool(0.5) {
+            right.append_all_left(other);
+            other = right;
+        } else {
+            other.append_all_right(right);
+        }
+        let (i, h) = (rng.random_range(0..4), rng.random_range(0..4));
+        assert_eq!(other.index_of(&handles[h]), Some(h));
+        assert_eq!(list.index_of(&handles[i]), None);
+        assert!((&other).debug_check_structural_integrity());
+    }
+
+    //a handle can be checked against one list while the list its element is in is mutated on another thread
+    let (mut a, b): (FoldList<u64, u64, monoids::Sum>, FoldList<u64, u64, monoids::Sum>) = ((0..100).collect(), (0..100).collect());
+    let handle = a.handle_at(50);
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            for i in 0..1000 {
+                let right = a.split_off(i % 100);
+                a.append_all_left(right);
+            }
+            a.remove_by_handle(&handle);
+        });
+        for _ in 0..1000 {
+            assert_eq!(b.index_of(&handle), None);
+        }
+    });
+}
+
+#[cfg(feature = "handles")]
+#[test]
+fn test_priority_queue() {
+    use foldlist::priority_queue::FoldPriorityQueue;
+    let mut queue = FoldPriorityQueue::new();
+    let a = queue.push(5, 'a');
+    let b = queue.push(3, 'b');
+    queue.push(3, 'c');
+    let mut other = FoldPriorityQueue::new();
+    let d = other.push(1, 'd');
+    queue.push(7, 'e');
+    assert_eq!(queue.peek_min(), Some((&3, &'b')));
+    assert_eq!(queue.change_priority(&a, 3), Some(5));
+    assert_eq!(queue.change_priority(&d, 0), None);
+    queue.meld(other);
+    assert_eq!((queue.len(), queue.get(&d)), (5, Some((&1, &'d'))));
+    assert_eq!(queue.change_priority(&b, 4), Some(3));
+    assert_eq!(queue.remove(&d), Some((1, 'd')));
+    let mut popped = vec![];
+    while let Some((_, t)) = queue.pop_min() {
+        popped.push(t);
+    }
+    assert_eq!(popped, vec!['a', 'c', 'b', 'e']);
+    assert_eq!((queue.peek_min(), queue.get(&a)), (None, None));
+
+    //a closure panicking partway through an update leaves the queue intact
+    let mut queue: FoldPriorityQueue<u32, char> = [(2, 'x'), (1, 'y'), (1, 'z')].into_iter().collect();
+    let handle = queue.push(u32::MAX, 'w');
+    assert!(catch_unwind(AssertUnwindSafe(|| queue.update_value(&handle, |t| { *t = 'v'; panic!("update failed") }))).is_err());
+    assert_eq!((queue.get(&handle), queue.peek_min(), queue.len()), (Some((&u32::MAX, &'v')), Some((&1, &'y')), 4));
+    //handles only work on the queue which their element is in
+    let mut other = FoldPriorityQueue::new();
+    let o = other.push(0, 'o');
+    assert_eq!((queue.change_priority(&o, 5), queue.remove(&o), other.get(&handle)), (None, None, None));
+    other.meld(std::mem::replace(&mut queue, FoldPriorityQueue::new()));
+    assert_eq!(queue.pop_min(), None);
+    assert_eq!((queue.get(&handle), other.get(&handle)), (None, Some((&u32::MAX, &'v'))));
+    //ties go to whichever was pushed first, even after a priority change
+    assert_eq!(other.change_priority(&handle, 0), Some(u32::MAX));
+    assert_eq!((other.pop_min(), other.pop_min(), other.get(&handle)), (Some((0, 'o')), Some((0, 'v')), None));
+    other.clear();
+    assert_eq!(other.pop_min(), None);
+    assert_eq!((other.peek_min(), other.min_priority()), (None, None));
+}
+
+#[test]
+fn test_fold_window() {
+    //string concatenation isn't invertible, or commutative
+    let mut window = FoldWindow::new(|a: String, b: String| a + &b, |c: &char| c.to_string(), String::new);
+    assert_eq!(window.fold(), "");
+    window.extend("abc".chars());
+    assert_eq!(window.pop_front(), Some('a'));
+    window.push_back('d');
+    assert_eq!((window.fold(), window.len(), window.front(), window.back()), ("bcd".to_string(), 3, Some(&'b'), Some(&'d')));
+    assert_eq!(window.push_back_bounded('e', 3), Some('b'));
+    assert_eq!((window.get(0), window.get(2), window.get(3)), (Some(&'c'), Some(&'e'), None));
+    assert_eq!(window.iter().rev().collect::<String>(), "edc");
+    let list = window.into_fold_list();
+    assert_eq!(list.view_drop_left(1).fold(), "de");
+    let window = FoldWindow::from_fold_list(list);
+    assert_eq!(window.fold(), "cde");
+
+    //the fold stays in order while elements move from the back of the window to the front, which happens whenever the front runs out
+    let mut window = FoldWindow::new(|a: String, b: String| a + &b, |c: &char| c.to_string(), String::new);
+    let mut deque = std::collections::VecDeque::new();
+    for (i, c) in ('a'..='z').enumerate() {
+        window.push_back(c);
+        deque.push_back(c);
+        if i % 3 == 2 {
+            assert_eq!((window.pop_front(), window.pop_front()), (deque.pop_front(), deque.pop_front()));
+        }
+        assert_eq!(window.fold(), deque.iter().collect::<String>());
+        assert_eq!((window.front(), window.get(deque.len() - 1)), (deque.front(), deque.back()));
+    }
+    //bounds at the extremes
+    assert_eq!((window.push_back_bounded('!', usize::MAX), window.get(usize::MAX), window.len()), (None, None, 11));
+    assert_eq!((window.push_back_bounded('?', 0), window.pop_front(), window.fold()), (Some('?'), None, String::new()));
+
+    //a panicking op leaves the window as it was, whether it's pushing or moving elements to the front
+    let fail = std::cell::Cell::new(false);
+    let mut window = FoldWindow::new(|a: String, b: String| if fail.get() { panic!("op failed") } else { a + &b }, |c: &char| c.to_string(), String::new);
+    window.extend("abc".chars());
+    fail.set(true);
+    assert!(catch_unwind(AssertUnwindSafe(|| window.push_back('d'))).is_err());
+    assert!(catch_unwind(AssertUnwindSafe(|| window.pop_front())).is_err());
+    fail.set(false);
+    assert_eq!((window.fold(), window.len()), ("abc".to_string(), 3));
+    assert_eq!((window.pop_front(), window.fold()), (Some('a'), "bc".to_string()));
+}
+
+#[test]
+fn test_invertible_settings() {
+    let settings = InvertibleFoldSettingsStruct {
+        settings: monoids::Sum,
+        subtract_closure: TupleFun(|a: i64, b: i64| a - b),
+        _m: PhantomData,
+    };
+    assert_eq!((settings.subtract(7, 3), settings.inverse(5)), (4, -5));
+    let mut list = FoldList::from_settings(settings);
+    list.append_right_from_iter([3, -1, 4, 1, -5].into_iter());
+    assert_eq!((list.prefix_fold(0), list.prefix_fold(2), list.prefix_fold(5)), (0, 2, 2));
+    assert_eq!(list.fold_range_by_prefixes(1..=3), 4);
+    assert_eq!(list.fold_ranges([0..5, 2..2, 3..5, 0..1]), vec![2, 0, -4, 3]);
+    assert_eq!(list.fold_ranges([5..5, 0..0, 4..5, 4..5]), vec![0, 0, -5, -5]);
+    assert_eq!((list.view_range(1..4).fold_by_prefixes(), list.view_drop_left(2).fold_by_prefixes(), list.view_range(2..2).fold_by_prefixes()), (4, 0, 0));
+    assert_eq!(list.view_reversed().view_drop_left(1).fold_by_prefixes(), 7);
+    assert_eq!(list.view_simplify(|d: &i64| d * 2, |(a,b)| a + b).view_range(1..4).fold_by_prefixes(), 8);
+    let xors: FoldList<u8, u8, monoids::Xor> = [0b101, 0b011, 0b110].into_iter().collect();
+    assert_eq!(xors.fold_ranges([1..3, 0..2]), vec![0b101, 0b110]);
+
+    //compare against a Vec
+    let mut rng = StdRng::seed_from_u64(20025);
+    let mut list: FoldList<u64, u64, monoids::Sum> = FoldList::default();
+    let mut vec: Vec<u64> = Vec::new();
+    for _ in 0..300 {
+        let index = rng.random_range(0..=vec.len());
+        if rng.random_bool(0.7) || vec.is_empty() {
+            let t = rng.random_range(0..1000);
+            list.insert_at(index, t);
+            vec.insert(index, t);
+        } else {
+            let index = index.min(vec.len() - 1);
+            assert_eq!(list.remove_at(index), vec.remove(index));
+        }
+        let index = rng.random_range(0..=vec.len());
+        assert_eq!(list.prefix_fold(index), vec[..index].iter().sum::<u64>());
+        let ranges: Vec<_> = (0..10).map(|_| {
+            let start = rng.random_range(0..=vec.len());
+            start..rng.random_range(start..=vec.len())
+        }).collect();
+        let expected: Vec<u64> = ranges.iter().map(|range| vec[range.clone()].iter().sum()).collect();
+        assert_eq!(list.fold_ranges(ranges.iter().cloned()), expected);
+        assert_eq!(list.fold_range_by_prefixes(ranges[0].clone()), expected[0]);
+        assert_eq!(list.view_range(ranges[1].clone()).fold_by_prefixes(), expected[1]);
+        assert_eq!(list.view_reversed().view_drop_left(vec.len() - ranges[2].end).view_drop_right(ranges[2].start).fold_by_prefixes(), expected[2]);
+    }
+}
+
+#[test]
+#[should_panic(expected = "Invalid range: the range starts at 2 but ends at 1")]
+fn test_fold_ranges_reversed() {
+    let list: FoldList<u64, u64, monoids::Sum> = [1].into_iter().collect();
+    #[allow(clippy::reversed_empty_ranges)]
+    list.fold_ranges([0..1, 2..1]);
+}
+
+#[test]
+#[should_panic(expected = "Index out of bounds: the index is 18446744073709551615 but the length is 1")]
+fn test_fold_ranges_usize_max() {
+    let list: FoldList<u64, u64, monoids::Sum> = [1].into_iter().collect();
+    list.fold_ranges([0..=usize::MAX]);
+}
+
+#[test]
+fn test_inverse_with_size() {
+    let settings = SettingsWithSize(InvertibleFoldSettingsStruct {
+        settings: monoids::Sum,
+        subtract_closure: TupleFun(|a: i64, b: i64| a - b),
+        _m: PhantomData,
+    });
+    assert_eq!((settings.subtract((5, 7), (2, 3)), settings.inverse((0, 4))), ((3, 4), (0, -4)));
+    assert!(catch_unwind(|| settings.inverse((2, 4))).unwrap_err().downcast_ref::<String>().unwrap().contains("sizes have no inverse"));
+}
+
 fn rand_char(rng: &mut impl Rng) -> char {
     let i = rng.random_range(0..64);
     let ret = match i {